@@ -0,0 +1,53 @@
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated from src/ffi.rs, do not edit. Regenerate with
+ * cbindgen --config cbindgen.toml --output include/aoc.h src/ffi.rs */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// The answer was written to `out`.
+#define AOC_OK 0
+
+// `input` or `out_len` was null, or `out` was null with a nonzero `out_cap`.
+#define AOC_ERR_NULL_POINTER 1
+
+// There is no solution for that year, day and part.
+#define AOC_ERR_UNKNOWN_PUZZLE 2
+
+// The input is not UTF-8.
+#define AOC_ERR_INVALID_INPUT 3
+
+// The solution failed or panicked; `out` holds the error message.
+#define AOC_ERR_SOLVE_FAILED 4
+
+// `out` is too small; `*out_len` is set to the size needed.
+#define AOC_ERR_BUFFER_TOO_SMALL 5
+
+// Solves one part of a puzzle with its main implementation.
+//
+// `input` points to `input_len` bytes of puzzle input. On `AOC_OK` and
+// `AOC_ERR_SOLVE_FAILED` the answer or error message is written to `out`
+// as a NUL-terminated string and `*out_len` is set to its length without
+// the NUL. When it does not fit in `out_cap` bytes nothing is written,
+// `*out_len` is set to the capacity needed including the NUL and
+// `AOC_ERR_BUFFER_TOO_SMALL` is returned, so a caller can retry.
+//
+// # Safety
+//
+// `input` must be valid for reads of `input_len` bytes, `out` for writes
+// of `out_cap` bytes and `out_len` for a write of one `size_t`.
+int32_t aoc_solve(uint32_t year,
+                  uint32_t day,
+                  uint32_t part,
+                  const uint8_t *input,
+                  size_t input_len,
+                  char *out,
+                  size_t out_cap,
+                  size_t *out_len);
+
+// A static, NUL-terminated description of an `AOC_*` code.
+const char *aoc_strerror(int32_t code);
+
+#endif  /* AOC_H */
@@ -0,0 +1,82 @@
+// dense visited set for grid cells, optionally with extra layers per cell
+// (e.g. one per facing direction), backed by one bit per (cell, layer)
+#[derive(Debug, Clone)]
+pub struct GridSet {
+    bits: Vec<u64>,
+    rows: usize,
+    cols: usize,
+    layers: usize,
+}
+
+impl GridSet {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self::with_layers(rows, cols, 1)
+    }
+
+    pub fn with_layers(rows: usize, cols: usize, layers: usize) -> Self {
+        GridSet {
+            bits: vec![0; (rows * cols * layers).div_ceil(64)],
+            rows,
+            cols,
+            layers,
+        }
+    }
+
+    fn index(&self, (row, col): (usize, usize), layer: usize) -> usize {
+        debug_assert!(row < self.rows && col < self.cols && layer < self.layers);
+        (row * self.cols + col) * self.layers + layer
+    }
+
+    // returns true if the cell was not in the set yet, like HashSet::insert
+    pub fn insert(&mut self, pos: (usize, usize)) -> bool {
+        self.insert_layer(pos, 0)
+    }
+
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        self.contains_layer(pos, 0)
+    }
+
+    // returns true if the cell was in the set, like HashSet::remove
+    pub fn remove(&mut self, pos: (usize, usize)) -> bool {
+        self.remove_layer(pos, 0)
+    }
+
+    pub fn insert_layer(&mut self, pos: (usize, usize), layer: usize) -> bool {
+        let i = self.index(pos, layer);
+        let mask = 1 << (i % 64);
+        let word = &mut self.bits[i / 64];
+        let inserted = *word & mask == 0;
+        *word |= mask;
+        inserted
+    }
+
+    pub fn remove_layer(&mut self, pos: (usize, usize), layer: usize) -> bool {
+        let i = self.index(pos, layer);
+        let mask = 1 << (i % 64);
+        let word = &mut self.bits[i / 64];
+        let removed = *word & mask != 0;
+        *word &= !mask;
+        removed
+    }
+
+    pub fn contains_layer(&self, pos: (usize, usize), layer: usize) -> bool {
+        let i = self.index(pos, layer);
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    // empties the set but keeps the allocation for the next round
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&word| word == 0)
+    }
+}
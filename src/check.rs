@@ -0,0 +1,501 @@
+use std::{
+    collections::{HashMap, HashSet},
+    panic,
+};
+
+use crate::{
+    inputs,
+    registry::{self, Solution},
+    rng::Rng,
+    runner,
+};
+
+// a randomized differential check: every registered implementation of the
+// part must give the same answer as the brute-force `reference` on inputs
+// from `generate`
+pub struct Property {
+    pub day: u32,
+    pub part: u32,
+    pub generate: fn(&mut Rng, usize) -> String,
+    pub reference: fn(&str) -> String,
+}
+
+pub static PROPERTIES: &[Property] = &[
+    Property {
+        day: 6,
+        part: 1,
+        generate: inputs::day6,
+        reference: day6_part1,
+    },
+    Property {
+        day: 6,
+        part: 2,
+        generate: inputs::day6,
+        reference: day6_part2,
+    },
+    Property {
+        day: 7,
+        part: 1,
+        generate: inputs::day7,
+        reference: day7_part1,
+    },
+    Property {
+        day: 7,
+        part: 2,
+        generate: inputs::day7,
+        reference: day7_part2,
+    },
+    Property {
+        day: 9,
+        part: 1,
+        generate: inputs::day9,
+        reference: day9_part1,
+    },
+    Property {
+        day: 9,
+        part: 2,
+        generate: inputs::day9,
+        reference: day9_part2,
+    },
+    Property {
+        day: 10,
+        part: 1,
+        generate: inputs::day10,
+        reference: day10_part1,
+    },
+    Property {
+        day: 10,
+        part: 2,
+        generate: inputs::day10,
+        reference: day10_part2,
+    },
+    Property {
+        day: 12,
+        part: 1,
+        generate: inputs::day12,
+        reference: day12_part1,
+    },
+    Property {
+        day: 12,
+        part: 2,
+        generate: inputs::day12,
+        reference: day12_part2,
+    },
+    Property {
+        day: 13,
+        part: 1,
+        generate: inputs::day13,
+        reference: day13_part1,
+    },
+];
+
+#[derive(Debug, Clone)]
+pub struct Failure {
+    pub solution: &'static Solution,
+    // the generated case that failed first
+    pub seed: u64,
+    pub size: usize,
+    // how many lines that case's input had, when `input` is shrunk from it
+    pub shrunk_from: Option<usize>,
+    pub input: String,
+    pub expected: String,
+    pub actual: Result<String, String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub cases: usize,
+    pub max_size: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cases: 200,
+            max_size: 12,
+            seed: 2024,
+        }
+    }
+}
+
+fn case_seed(seed: u64, case: usize) -> u64 {
+    Rng::new(seed ^ (case as u64).wrapping_mul(0x2545_f491_4f6c_dd1d)).next_u64()
+}
+
+// runs one generated case against every implementation, panics count as
+// failures too
+fn run_case(property: &Property, seed: u64, size: usize) -> Option<Failure> {
+    let input = (property.generate)(&mut Rng::new(seed), size);
+    let expected = (property.reference)(&input);
+    for solution in registry::implementations(property.day, property.part) {
+        let actual = runner::solve_catching(solution, &input);
+        if actual.as_ref() != Ok(&expected) {
+            return Some(Failure {
+                solution,
+                seed,
+                size,
+                shrunk_from: None,
+                input,
+                expected,
+                actual,
+            });
+        }
+    }
+    None
+}
+
+// `input` as a smaller failure than `failure`: the same implementation
+// still disagrees with the reference the same way, a wrong answer staying a
+// wrong answer and an error an error. Inputs the reference panics on are no
+// valid puzzle and don't count.
+fn fails_alike(property: &Property, failure: &Failure, input: &str) -> Option<Failure> {
+    let expected = panic::catch_unwind(|| (property.reference)(input)).ok()?;
+    let actual = runner::solve_catching(failure.solution, input);
+    let alike = actual.as_ref() != Ok(&expected) && actual.is_ok() == failure.actual.is_ok();
+    alike.then(|| Failure {
+        input: input.to_string(),
+        expected,
+        actual,
+        ..failure.clone()
+    })
+}
+
+// the input with one blank-line separated block left out, for every block,
+// then the same for lines, then for the space-separated items on a line
+fn smaller_inputs(input: &str) -> Vec<String> {
+    let mut smaller = vec![];
+    let blocks: Vec<&str> = input.split("\n\n").collect();
+    for skip in 0..blocks.len() {
+        let mut kept = blocks.clone();
+        kept.remove(skip);
+        smaller.push(kept.join("\n\n"));
+    }
+    let lines: Vec<&str> = input.split('\n').collect();
+    for skip in 0..lines.len() {
+        let mut kept = lines.clone();
+        kept.remove(skip);
+        smaller.push(kept.join("\n"));
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let items: Vec<&str> = line.split(' ').collect();
+        if items.len() < 2 {
+            continue;
+        }
+        for skip in 0..items.len() {
+            let mut kept = items.clone();
+            kept.remove(skip);
+            let mut lines = lines.clone();
+            let line = kept.join(" ");
+            lines[i] = &line;
+            smaller.push(lines.join("\n"));
+        }
+    }
+    smaller
+}
+
+// keeps taking the first smaller input that still fails alike until none
+// does, so every line and item left is needed for the failure
+fn shrink(property: &Property, mut failure: Failure) -> Failure {
+    let original = failure.input.clone();
+    while let Some(smaller) = smaller_inputs(&failure.input)
+        .iter()
+        .find_map(|input| fails_alike(property, &failure, input))
+    {
+        failure = smaller;
+    }
+    if failure.input != original {
+        failure.shrunk_from = Some(original.lines().count());
+    }
+    failure
+}
+
+// sizes grow over the run; the first failing case is shrunk before it is
+// reported
+pub fn check(property: &Property, config: Config) -> Result<(), Failure> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = check_quietly(property, config);
+    panic::set_hook(hook);
+    result
+}
+
+fn check_quietly(property: &Property, config: Config) -> Result<(), Failure> {
+    for case in 0..config.cases {
+        let size = 1 + case * config.max_size / config.cases.max(1);
+        if let Some(failure) = run_case(property, case_seed(config.seed, case), size) {
+            return Err(shrink(property, failure));
+        }
+    }
+    Ok(())
+}
+
+// the brute-force references below favour obviously-correct over fast
+
+fn lines(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+fn neighbour(
+    grid: &[Vec<u8>],
+    (r, c): (usize, usize),
+    (dr, dc): (isize, isize),
+) -> Option<(usize, usize)> {
+    let (r, c) = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+    (r < grid.len() && c < grid[r].len()).then_some((r, c))
+}
+
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+fn find(grid: &[Vec<u8>], value: u8) -> Vec<(usize, usize)> {
+    let mut found = vec![];
+    for (r, row) in grid.iter().enumerate() {
+        for (c, &cell) in row.iter().enumerate() {
+            if cell == value {
+                found.push((r, c));
+            }
+        }
+    }
+    found
+}
+
+// cells the guard walks through, None if it ends up in a loop
+fn guard_path(grid: &[Vec<u8>]) -> Option<HashSet<(usize, usize)>> {
+    let mut pos = find(grid, b'^')[0];
+    let mut dir = 0;
+    let mut states = HashSet::new();
+    while states.insert((pos, dir)) {
+        match neighbour(grid, pos, DIRECTIONS[dir]) {
+            None => return Some(states.into_iter().map(|(pos, _)| pos).collect()),
+            Some(next) if grid[next.0][next.1] == b'#' => dir = (dir + 1) % 4,
+            Some(next) => pos = next,
+        }
+    }
+    None
+}
+
+fn day6_part1(input: &str) -> String {
+    guard_path(&lines(input)).unwrap().len().to_string()
+}
+
+fn day6_part2(input: &str) -> String {
+    let grid = lines(input);
+    let mut loops = 0;
+    for (r, c) in find(&grid, b'.') {
+        let mut blocked = grid.clone();
+        blocked[r][c] = b'#';
+        if guard_path(&blocked).is_none() {
+            loops += 1;
+        }
+    }
+    loops.to_string()
+}
+
+// every value the numbers can make, trying all operator sequences
+fn day7_values(nums: &[u128], with_concat: bool) -> Vec<u128> {
+    let (&last, rest) = nums.split_last().unwrap();
+    if rest.is_empty() {
+        return vec![last];
+    }
+    let mut values = vec![];
+    for value in day7_values(rest, with_concat) {
+        values.push(value + last);
+        values.push(value * last);
+        if with_concat {
+            values.push(format!("{}{}", value, last).parse().unwrap());
+        }
+    }
+    values
+}
+
+fn day7(input: &str, with_concat: bool) -> String {
+    let mut total = 0;
+    for line in input.lines() {
+        let (target, nums) = line.split_once(": ").unwrap();
+        let target: u128 = target.parse().unwrap();
+        let nums: Vec<u128> = nums.split(' ').map(|n| n.parse().unwrap()).collect();
+        if day7_values(&nums, with_concat).contains(&target) {
+            total += target;
+        }
+    }
+    total.to_string()
+}
+
+fn day7_part1(input: &str) -> String {
+    day7(input, false)
+}
+
+fn day7_part2(input: &str) -> String {
+    day7(input, true)
+}
+
+// one entry per block, the file id or None for free space
+fn day9_blocks(input: &str) -> Vec<Option<usize>> {
+    let mut blocks = vec![];
+    for (i, digit) in input.trim().bytes().enumerate() {
+        let id = (i % 2 == 0).then_some(i / 2);
+        blocks.extend(std::iter::repeat_n(id, (digit - b'0') as usize));
+    }
+    blocks
+}
+
+fn day9_checksum(blocks: &[Option<usize>]) -> String {
+    let checksum: usize = blocks
+        .iter()
+        .enumerate()
+        .map(|(i, id)| i * id.unwrap_or(0))
+        .sum();
+    checksum.to_string()
+}
+
+fn day9_part1(input: &str) -> String {
+    let mut blocks = day9_blocks(input);
+    while let Some(free) = blocks.iter().position(Option::is_none) {
+        let last = blocks.iter().rposition(Option::is_some).unwrap();
+        if last < free {
+            break;
+        }
+        blocks.swap(free, last);
+    }
+    day9_checksum(&blocks)
+}
+
+fn day9_part2(input: &str) -> String {
+    let mut blocks = day9_blocks(input);
+    let max_id = blocks.iter().flatten().max().copied().unwrap_or(0);
+    for id in (0..=max_id).rev() {
+        let start = blocks.iter().position(|&b| b == Some(id)).unwrap();
+        let len = blocks.iter().filter(|&&b| b == Some(id)).count();
+        let free = (0..start).find(|&i| blocks[i..i + len].iter().all(Option::is_none));
+        if let Some(free) = free {
+            for i in 0..len {
+                blocks.swap(free + i, start + i);
+            }
+        }
+    }
+    day9_checksum(&blocks)
+}
+
+// every hiking trail from the trailhead, as the summit it ends at
+fn day10_trails(grid: &[Vec<u8>], pos: (usize, usize), summits: &mut Vec<(usize, usize)>) {
+    let height = grid[pos.0][pos.1];
+    if height == b'9' {
+        summits.push(pos);
+        return;
+    }
+    for dir in DIRECTIONS {
+        if let Some(next) = neighbour(grid, pos, dir)
+            && grid[next.0][next.1] == height + 1
+        {
+            day10_trails(grid, next, summits);
+        }
+    }
+}
+
+fn day10(input: &str, distinct_trails: bool) -> String {
+    let grid = lines(input);
+    let mut total = 0;
+    for trailhead in find(&grid, b'0') {
+        let mut summits = vec![];
+        day10_trails(&grid, trailhead, &mut summits);
+        if !distinct_trails {
+            summits.sort();
+            summits.dedup();
+        }
+        total += summits.len();
+    }
+    total.to_string()
+}
+
+fn day10_part1(input: &str) -> String {
+    day10(input, false)
+}
+
+fn day10_part2(input: &str) -> String {
+    day10(input, true)
+}
+
+// region id of every plot, by flood fill
+fn day12_regions(grid: &[Vec<u8>]) -> HashMap<(usize, usize), usize> {
+    let mut region_of = HashMap::new();
+    for start in (0..grid.len()).flat_map(|r| (0..grid[r].len()).map(move |c| (r, c))) {
+        if region_of.contains_key(&start) {
+            continue;
+        }
+        let id = region_of.len();
+        let mut stack = vec![start];
+        region_of.insert(start, id);
+        while let Some(pos) = stack.pop() {
+            for dir in DIRECTIONS {
+                if let Some(next) = neighbour(grid, pos, dir)
+                    && grid[next.0][next.1] == grid[pos.0][pos.1]
+                    && !region_of.contains_key(&next)
+                {
+                    region_of.insert(next, id);
+                    stack.push(next);
+                }
+            }
+        }
+    }
+    region_of
+}
+
+// fence pieces per region: a plot side facing another region or the edge.
+// With `sides`, a piece only counts if the plot before it along the fence
+// (to the left when facing out) does not continue the same fence
+fn day12(input: &str, sides: bool) -> String {
+    let grid = lines(input);
+    let region_of = day12_regions(&grid);
+    let fenced = |pos: (usize, usize), dir: (isize, isize)| {
+        neighbour(&grid, pos, dir).and_then(|n| region_of.get(&n)) != region_of.get(&pos)
+    };
+    let mut area: HashMap<usize, usize> = HashMap::new();
+    let mut fences: HashMap<usize, usize> = HashMap::new();
+    for (&pos, &id) in &region_of {
+        *area.entry(id).or_default() += 1;
+        for dir in DIRECTIONS {
+            if !fenced(pos, dir) {
+                continue;
+            }
+            let along = (-dir.1, dir.0);
+            let continues = neighbour(&grid, pos, along)
+                .is_some_and(|prev| region_of[&prev] == id && fenced(prev, dir));
+            if !sides || !continues {
+                *fences.entry(id).or_default() += 1;
+            }
+        }
+    }
+    let price: usize = area
+        .iter()
+        .map(|(id, area)| area * fences.get(id).unwrap_or(&0))
+        .sum();
+    price.to_string()
+}
+
+fn day12_part1(input: &str) -> String {
+    day12(input, false)
+}
+
+fn day12_part2(input: &str) -> String {
+    day12(input, true)
+}
+
+fn day13_part1(input: &str) -> String {
+    let mut tokens = 0;
+    for machine in input.split("\n\n") {
+        let nums: Vec<i64> = machine
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let cheapest = (0..=100)
+            .flat_map(|a| (0..=100).map(move |b| (a, b)))
+            .filter(|&(a, b)| {
+                nums[0] * a + nums[2] * b == nums[4] && nums[1] * a + nums[3] * b == nums[5]
+            })
+            .map(|(a, b)| 3 * a + b)
+            .min();
+        tokens += cheapest.unwrap_or(0);
+    }
+    tokens.to_string()
+}
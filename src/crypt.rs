@@ -0,0 +1,159 @@
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    ChaCha20Poly1305, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+};
+
+// puzzle inputs can be committed as dayN.txt.enc next to where dayN.txt
+// would be: "AOC1", a 12 byte nonce, then the ChaCha20-Poly1305 ciphertext.
+// The 32 byte key is hex, from AOC_INPUT_KEY or else the keyfile, which is
+// kept out of git.
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+pub const KEY_FILE: &str = ".aoc-input-key";
+
+const MAGIC: &[u8; 4] = b"AOC1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum CryptError {
+    NoKey,
+    BadKey(String),
+    Corrupt(PathBuf),
+    Io(io::Error),
+}
+
+impl fmt::Display for CryptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoKey => write!(f, "no key, set {} or create {}", KEY_ENV, KEY_FILE),
+            Self::BadKey(why) => write!(f, "bad key: {}", why),
+            Self::Corrupt(path) => {
+                write!(f, "{} is damaged or needs another key", path.display())
+            }
+            Self::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for CryptError {}
+
+impl From<io::Error> for CryptError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+pub struct Key(ChaCha20Poly1305);
+
+impl Key {
+    pub fn from_hex(hex: &str) -> Result<Self, CryptError> {
+        let hex = hex.trim();
+        if hex.len() != 64 {
+            return Err(CryptError::BadKey(format!(
+                "expected 64 hex digits, found {}",
+                hex.len()
+            )));
+        }
+        let bytes: Option<Vec<u8>> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect();
+        let bytes = bytes.ok_or(CryptError::BadKey("not hex".to_string()))?;
+        Ok(Key(ChaCha20Poly1305::new_from_slice(&bytes).unwrap()))
+    }
+
+    // the environment variable wins over the keyfile
+    pub fn load() -> Result<Self, CryptError> {
+        if let Ok(hex) = env::var(KEY_ENV) {
+            return Key::from_hex(&hex);
+        }
+        match fs::read_to_string(KEY_FILE) {
+            Ok(hex) => Key::from_hex(&hex),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(CryptError::NoKey),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self.0.encrypt(&nonce, plaintext).unwrap();
+        [MAGIC.as_slice(), nonce.as_slice(), &ciphertext].concat()
+    }
+
+    // None when the data was not made by `encrypt` with this key
+    pub fn decrypt(&self, data: &[u8]) -> Option<Vec<u8>> {
+        let rest = data.strip_prefix(MAGIC)?;
+        if rest.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        self.0.decrypt(Nonce::from_slice(nonce), ciphertext).ok()
+    }
+}
+
+pub fn generate_key_hex() -> String {
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    key.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+pub fn read_encrypted(path: &Path, key: &Key) -> Result<String, CryptError> {
+    let plaintext = key
+        .decrypt(&fs::read(path)?)
+        .ok_or_else(|| CryptError::Corrupt(path.to_path_buf()))?;
+    String::from_utf8(plaintext).map_err(|_| CryptError::Corrupt(path.to_path_buf()))
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|e| e == extension) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// writes dayN.txt.enc for every dayN.txt in `dir`; ones whose .enc already
+// holds the same text are left alone, so a fresh nonce doesn't show up as a
+// change in git. Returns the files written.
+pub fn encrypt_dir(dir: &Path, key: &Key) -> Result<Vec<PathBuf>, CryptError> {
+    let mut written = vec![];
+    for path in files_with_extension(dir, "txt")? {
+        let plaintext = fs::read(&path)?;
+        let target = encrypted_path(&path);
+        let existing = fs::read(&target).ok().and_then(|data| key.decrypt(&data));
+        if existing.as_ref() != Some(&plaintext) {
+            fs::write(&target, key.encrypt(&plaintext))?;
+            written.push(target);
+        }
+    }
+    Ok(written)
+}
+
+// the other way round, dayN.txt from every dayN.txt.enc in `dir`
+pub fn decrypt_dir(dir: &Path, key: &Key) -> Result<Vec<PathBuf>, CryptError> {
+    let mut written = vec![];
+    for path in files_with_extension(dir, "enc")? {
+        let plaintext = read_encrypted(&path, key)?;
+        let target = path.with_extension("");
+        if fs::read_to_string(&target).ok().as_ref() != Some(&plaintext) {
+            fs::write(&target, plaintext)?;
+            written.push(target);
+        }
+    }
+    Ok(written)
+}
@@ -1,26 +1,24 @@
-use aoc_runner_derive::aoc_generator;
 use aoc_runner_derive::aoc;
+use aoc_runner_derive::aoc_generator;
 
-#[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> (Vec<i32>, Vec<i32>) {
-
+use crate::hash::FastHashMap;
+use crate::parse::{self, ParseError};
 
+#[aoc_generator(day1)]
+pub fn input_generator(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut first_vec: Vec<i32> = Vec::new();
     let mut second_vec: Vec<i32> = Vec::new();
-    let mut lines = input.lines();
-    while let Some(line) = lines.next() {
-        let nums: Vec<i32> = line.split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
-        first_vec.push(nums[0]);
-        second_vec.push(nums[1]);
+    for line in input.lines() {
+        let [first, second] = parse::ints_exact(line)?;
+        first_vec.push(first);
+        second_vec.push(second);
     }
-     
+
     //sort the vectors
     first_vec.sort();
     second_vec.sort();
-    
-    (first_vec, second_vec)
+
+    Ok((first_vec, second_vec))
 }
 
 #[aoc(day1, part1)]
@@ -37,16 +35,30 @@ pub fn part1(input: &(Vec<i32>, Vec<i32>)) -> u32 {
 
 #[aoc(day1, part2)]
 pub fn part2(input: &(Vec<i32>, Vec<i32>)) -> u32 {
+    let (first_vec, second_vec) = input;
+    let mut counts: FastHashMap<i32, i32> = FastHashMap::default();
+    for &second in second_vec {
+        *counts.entry(second).or_insert(0) += 1;
+    }
+    let mut sum = 0;
+    for first in first_vec {
+        sum += first * counts.get(first).copied().unwrap_or(0);
+    }
+    sum as u32
+}
+
+#[aoc(day1, part2, Naive)]
+pub fn part2_naive(input: &(Vec<i32>, Vec<i32>)) -> u32 {
     let (first_vec, second_vec) = input;
     let mut sum = 0;
-    for i in 0..first_vec.len() {
+    for first in first_vec {
         let mut count = 0;
-        for j in 0..second_vec.len() {
-            if first_vec[i] == second_vec[j] {
+        for second in second_vec {
+            if first == second {
                 count += 1;
             }
         }
-        sum += first_vec[i] * count;
+        sum += first * count;
     }
     sum as u32
-}
\ No newline at end of file
+}
@@ -1,55 +1,45 @@
-use std::collections::{HashMap, HashSet};
+use aoc_runner_derive::aoc;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use crate::bitset::GridSet;
+use crate::grid::Grid;
+use crate::hash::{FastHashMap, FastHashSet};
+use crate::parse::ParseError;
 
-#[aoc_generator(day10)]
-pub fn input_generator(input: &str) -> Vec<Vec<u8>> {
-    let mut grid = vec![];
-    for line in input.lines() {
-        grid.push(
-            line.chars()
-                .map(|x| x.to_digit(10).unwrap() as u8)
-                .collect(),
-        );
-    }
-    grid
-}
-
-pub fn find_trailheads(grid: &Vec<Vec<u8>>) -> Vec<(usize, usize)> {
+// heights stay as their ASCII digits, b'0' to b'9'
+pub fn find_trailheads(grid: &Grid) -> Vec<(usize, usize)> {
     let mut pos = vec![];
-    for (i, row) in grid.iter().enumerate() {
-        for (j, ele) in row.iter().enumerate() {
-            if *ele == 0 {
-                pos.push((i, j));
-            }
+    for (coord, ele) in grid.iter() {
+        if ele == b'0' {
+            pos.push(coord);
         }
     }
     pos
 }
 
 pub fn reachable_summits_from_trailhead(
-    grid: &Vec<Vec<u8>>,
+    grid: &Grid,
     trailhead_pos: (usize, usize),
-) -> HashSet<(usize, usize)> {
-    let mut reachable_summits = HashSet::new();
+    visited: &mut GridSet,
+) -> FastHashSet<(usize, usize)> {
+    let mut reachable_summits = FastHashSet::default();
     //BFS
     let mut queue = vec![trailhead_pos];
-    let mut visited = HashSet::new();
+    visited.clear();
     visited.insert(trailhead_pos);
 
     while !queue.is_empty() {
         let (r, c) = queue.remove(0);
-        let height = grid[r][c];
+        let height = grid[(r, c)];
 
         for (dr, dc) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
-            let (next_r, next_c) = (r as i32 + dr as i32, c as i32 + dc as i32);
+            let (next_r, next_c) = (r as i32 + dr, c as i32 + dc);
             let (next_r, next_c) = (next_r as usize, next_c as usize);
 
-            if next_r < grid.len() && next_c < grid[0].len() {
-                let next_height = grid[next_r][next_c];
-                if next_height == height + 1 && !visited.contains(&(next_r, next_c)) {
+            if grid.in_bounds((next_r, next_c)) {
+                let next_height = grid[(next_r, next_c)];
+                if next_height == height + 1 && !visited.contains((next_r, next_c)) {
                     visited.insert((next_r, next_c));
-                    if next_height == 9 {
+                    if next_height == b'9' {
                         reachable_summits.insert((next_r, next_c));
                     } else {
                         queue.push((next_r, next_c));
@@ -62,36 +52,38 @@ pub fn reachable_summits_from_trailhead(
 }
 
 #[aoc(day10, part1)]
-pub fn part1(grid: &Vec<Vec<u8>>) -> usize {
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let grid = Grid::parse(input)?;
     let mut total_score = 0;
-    for trailhead in find_trailheads(grid) {
-        total_score += reachable_summits_from_trailhead(grid, trailhead).len();
+    let mut visited = GridSet::new(grid.rows(), grid.cols());
+    for trailhead in find_trailheads(&grid) {
+        total_score += reachable_summits_from_trailhead(&grid, trailhead, &mut visited).len();
     }
-    total_score
+    Ok(total_score)
 }
 
 fn count_paths(
-    grid: &Vec<Vec<u8>>,
+    grid: &Grid,
     current_pos: (usize, usize),
-    memo_cache: &mut HashMap<(usize, usize), usize>,
+    memo_cache: &mut FastHashMap<(usize, usize), usize>,
 ) -> usize {
     //memo
     if let Some(&cached) = memo_cache.get(&current_pos) {
         return cached;
     }
     //base case
-    let height = grid[current_pos.0][current_pos.1];
-    if height == 9 {
+    let height = grid[current_pos];
+    if height == b'9' {
         memo_cache.insert(current_pos, 1);
         return 1;
     }
 
     let mut num_paths = 0;
     for (dr, dc) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
-        let (next_r, next_c) = (current_pos.0 as i32 + dr as i32, current_pos.1 as i32 + dc as i32);
+        let (next_r, next_c) = (current_pos.0 as i32 + dr, current_pos.1 as i32 + dc);
         let (next_r, next_c) = (next_r as usize, next_c as usize);
 
-        if next_r < grid.len() && next_c < grid[0].len() && grid[next_r][next_c] == height + 1 {
+        if grid.in_bounds((next_r, next_c)) && grid[(next_r, next_c)] == height + 1 {
             num_paths += count_paths(grid, (next_r, next_c), memo_cache);
         }
     }
@@ -100,11 +92,12 @@ fn count_paths(
 }
 
 #[aoc(day10, part2)]
-pub fn part2(grid: &Vec<Vec<u8>>) -> usize {
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    let grid = Grid::parse(input)?;
     let mut total_score = 0;
-    let mut memo_cache = HashMap::new();
-    for trailhead in find_trailheads(grid) {
-        total_score += count_paths(grid, trailhead, &mut memo_cache);
+    let mut memo_cache = FastHashMap::default();
+    for trailhead in find_trailheads(&grid) {
+        total_score += count_paths(&grid, trailhead, &mut memo_cache);
     }
-    total_score
+    Ok(total_score)
 }
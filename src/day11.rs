@@ -1,23 +1,24 @@
-use std::collections::HashMap;
-
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::hash::FastHashMap;
+use crate::parse::{self, ParseError};
+
 #[aoc_generator(day11)]
-pub fn input_generator(input: &str) -> HashMap<u128, u128> {
-    let mut numbers = HashMap::new();
-    for num in input.split_ascii_whitespace() {
+pub fn input_generator(input: &str) -> Result<FastHashMap<u128, u128>, ParseError> {
+    let mut numbers = FastHashMap::default();
+    for num in parse::ints(input)? {
         numbers
-            .entry(num.parse().unwrap())
+            .entry(num)
             .and_modify(|count| *count += 1)
             .or_insert(1);
     }
-    numbers
+    Ok(numbers)
 }
 
 const ODD_LENGTH_MULTIPLIER: u128 = 2024;
 
-fn blink_once(stones: &mut HashMap<u128, u128>) {
-    let mut next_stones: HashMap<u128, u128> = HashMap::new();
+fn blink_once(stones: &mut FastHashMap<u128, u128>) -> Result<(), String> {
+    let mut next_stones: FastHashMap<u128, u128> = FastHashMap::default();
     for (&stone, &count) in stones.iter() {
         if stone == 0 {
             *next_stones.entry(1).or_insert(0) += count;
@@ -31,30 +32,30 @@ fn blink_once(stones: &mut HashMap<u128, u128>) {
             *next_stones.entry(left_val).or_insert(0) += count;
             *next_stones.entry(right_val).or_insert(0) += count;
         } else {
-            if let Some(new_stone_val) = stone.checked_mul(ODD_LENGTH_MULTIPLIER) {
-                *next_stones.entry(new_stone_val).or_insert(0) += count;
-            } else {
-                panic!("Overflow occurred");
-            }
+            let new_stone_val = stone
+                .checked_mul(ODD_LENGTH_MULTIPLIER)
+                .ok_or(format!("stone {} overflows when multiplied", stone))?;
+            *next_stones.entry(new_stone_val).or_insert(0) += count;
         }
     }
     *stones = next_stones;
+    Ok(())
 }
 
 #[aoc(day11, part1)]
-pub fn part1(numbers: &HashMap<u128, u128>) -> usize {
+pub fn part1(numbers: &FastHashMap<u128, u128>) -> Result<usize, String> {
     let mut stones = numbers.clone();
     for _ in 0..25 {
-        blink_once(&mut stones);
+        blink_once(&mut stones)?;
     }
-    stones.values().map(|&v| v as usize).sum()
+    Ok(stones.values().map(|&v| v as usize).sum())
 }
 
 #[aoc(day11, part2)]
-pub fn part2(numbers: &HashMap<u128, u128>) -> u128 {
+pub fn part2(numbers: &FastHashMap<u128, u128>) -> Result<u128, String> {
     let mut stones = numbers.clone();
     for _ in 0..75 {
-        blink_once(&mut stones);
+        blink_once(&mut stones)?;
     }
-    stones.values().map(|&v| v as u128).sum::<u128>()
+    Ok(stones.values().copied().sum::<u128>())
 }
@@ -1,20 +1,17 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_runner_derive::aoc;
 
-#[aoc_generator(day12)]
-pub fn grid_generator(input: &str) -> Vec<Vec<char>> {
-    let mut grid: Vec<Vec<char>> = Vec::new();
-    for line in input.lines() {
-        grid.push(line.chars().collect::<Vec<char>>());
-    }
-    grid
-}
+use crate::bitset::GridSet;
+use crate::grid::Grid;
+use crate::hash::FastHashSet;
+use crate::parallel;
+use crate::parse::ParseError;
 
 #[derive(Debug)]
 struct Region {
-    _plant: char,
-    plots: HashSet<(usize, usize)>,
+    _plant: u8,
+    plots: FastHashSet<(usize, usize)>,
 }
 
 impl Region {
@@ -82,14 +79,14 @@ impl Region {
     }
 }
 
-fn get_regions(grid: &Vec<Vec<char>>) -> Vec<Region> {
+fn get_regions(grid: &Grid) -> Vec<Region> {
     let mut regions = vec![];
-    let mut visited = HashSet::new();
-    for r in 0..grid.len() {
-        for c in 0..grid[0].len() {
-            if !visited.contains(&(r, c)) {
-                let plant = grid[r][c];
-                let mut plots = HashSet::new();
+    let mut visited = GridSet::new(grid.rows(), grid.cols());
+    for r in 0..grid.rows() {
+        for c in 0..grid.cols() {
+            if !visited.contains((r, c)) {
+                let plant = grid[(r, c)];
+                let mut plots = FastHashSet::default();
                 let mut queue = VecDeque::new();
 
                 visited.insert((r, c));
@@ -102,13 +99,13 @@ fn get_regions(grid: &Vec<Vec<char>>) -> Vec<Region> {
                         let next_c = curr_c as i32 + dc;
 
                         if next_r >= 0
-                            && next_r < grid.len() as i32
+                            && next_r < grid.rows() as i32
                             && next_c >= 0
-                            && next_c < grid[0].len() as i32
+                            && next_c < grid.cols() as i32
                         {
                             let nr = next_r as usize;
                             let nc = next_c as usize;
-                            if grid[nr][nc] == plant && !visited.contains(&(nr, nc)) {
+                            if grid[(nr, nc)] == plant && !visited.contains((nr, nc)) {
                                 visited.insert((nr, nc));
                                 plots.insert((nr, nc));
                                 queue.push_back((nr, nc));
@@ -128,23 +125,16 @@ fn get_regions(grid: &Vec<Vec<char>>) -> Vec<Region> {
 }
 
 #[aoc(day12, part1)]
-pub fn part1(grid: &Vec<Vec<char>>) -> usize {
-    let regions = get_regions(grid);
-    regions
-        .iter()
-        .map(|r| r.price(grid.len(), grid[0].len()))
-        .sum::<usize>()
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let grid = Grid::parse(input)?;
+    let regions = get_regions(&grid);
+    let prices = parallel::map(&regions, |r| r.price(grid.rows(), grid.cols()));
+    Ok(prices.into_iter().sum::<usize>())
 }
 
 #[aoc(day12, part2)]
-pub fn part2(grid: &Vec<Vec<char>>) -> usize {
-    let regions = get_regions(grid);
-    let mut p = 0;
-    for r in regions {
-        let s = r.sides();
-        println!("{}", r._plant);
-        dbg!(s);
-        p += s* r.area();
-    }
-    p
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    let regions = get_regions(&Grid::parse(input)?);
+    let prices = parallel::map(&regions, |r| r.sides() * r.area());
+    Ok(prices.into_iter().sum::<usize>())
 }
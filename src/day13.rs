@@ -1,7 +1,6 @@
-use std::i128;
-
 use aoc_runner_derive::{aoc, aoc_generator};
-use regex::Regex;
+
+use crate::parse::{self, ParseError};
 
 #[derive(Debug, Clone)]
 pub struct Prize {
@@ -11,35 +10,30 @@ pub struct Prize {
 }
 
 #[aoc_generator(day13)]
-pub fn input_generator(input: &str) -> Vec<Prize> {
+pub fn input_generator(input: &str) -> Result<Vec<Prize>, ParseError> {
     let mut prizes = vec![];
-    let regex = Regex::new(
-        r"Button A: X\+(\d+), Y\+(\d+)\s*Button B: X\+(\d+), Y\+(\d+)\s*Prize: X=(\d+), Y=(\d+)",
-    )
-    .unwrap();
-
-    let lines: Vec<_> = input.lines().collect();
-    for chunk in lines.chunks(4) {
-        let text = chunk.join("\n");
-        if let Some(caps) = regex.captures(&text) {
-            let button_a_x = caps[1].parse::<i128>().unwrap();
-            let button_a_y = caps[2].parse::<i128>().unwrap();
-            let button_b_x = caps[3].parse::<i128>().unwrap();
-            let button_b_y = caps[4].parse::<i128>().unwrap();
-            let prize_x = caps[5].parse::<i128>().unwrap();
-            let prize_y = caps[6].parse::<i128>().unwrap();
+    for block in parse::blocks(input) {
+        let [
+            button_a_x,
+            button_a_y,
+            button_b_x,
+            button_b_y,
+            prize_x,
+            prize_y,
+        ] = parse::ints_exact(block)?;
 
-            prizes.push(Prize {
-                button_a: (button_a_x, button_a_y),
-                button_b: (button_b_x, button_b_y),
-                coords: (prize_x, prize_y),
-            });
-        }
+        prizes.push(Prize {
+            button_a: (button_a_x, button_a_y),
+            button_b: (button_b_x, button_b_y),
+            coords: (prize_x, prize_y),
+        });
     }
-    prizes
+    Ok(prizes)
 }
 
-pub fn solve(p: &Prize) -> Option<i128> {
+// cheapest presses of (A, B) that reach the prize, pressing neither button
+// more than `max_presses` times if there is a limit
+pub fn solve(p: &Prize, max_presses: Option<i128>) -> Option<(i128, i128)> {
     /*
     x1*a + x2*b = p1
     y1*a + y2*b = p2
@@ -52,61 +46,110 @@ pub fn solve(p: &Prize) -> Option<i128> {
     let (y1, y2) = (p.button_a.1, p.button_b.1);
     let (p1, p2) = (p.coords.0, p.coords.1);
 
-    let d = (x1 * y2) as i128 - (y1 * x2) as i128;
-    let da = (p1 * y2) as i128 - (p2 * x2) as i128;
-    let db = (x1 * p2) as i128 - (y1 * p1) as i128;
+    let d = (x1 * y2) - (y1 * x2);
+    let da = (p1 * y2) - (p2 * x2);
+    let db = (x1 * p2) - (y1 * p1);
+    let within_limit = |presses: i128| max_presses.is_none_or(|max| presses <= max);
     if d != 0 {
-        println!("1");
-        let (a, b) = ((da / d) as i128, (db / d) as i128);
-        if x1 * a + x2 * b == p1 && y1 * a + y2 * b == p2 {
-            return Some((3 * a + b) as i128);
+        let (a, b) = ((da / d), (db / d));
+        // the buttons can't be pressed a negative number of times
+        if a >= 0
+            && b >= 0
+            && within_limit(a)
+            && within_limit(b)
+            && x1 * a + x2 * b == p1
+            && y1 * a + y2 * b == p2
+        {
+            Some((a, b))
         } else {
-            return None;
+            None
         }
     } else if da != 0 || db != 0 {
-        println!("None");
-        return None;
+        None
     } else {
-        println!("inf");
-        let (a, b) = solve_linear(x1, x2, p1);
-        return Some(3 * a + b);
+        // both buttons and the prize lie on one line through the origin, so
+        // one axis is enough unless the buttons don't move along it at all
+        let (a, b) = match x1 != 0 || x2 != 0 {
+            true => solve_linear(x1, x2, p1, max_presses)?,
+            false => solve_linear(y1, y2, p2, max_presses)?,
+        };
+        (x1 * a + x2 * b == p1 && y1 * a + y2 * b == p2).then_some((a, b))
     }
 }
 
-pub fn solve_linear(x: i128, y: i128, p: i128) -> (i128, i128) {
-    let (mut current_a, mut current_b) = (100000, 100000);
-    for a in 0..=p / x {
-        let b = (p - x * a) / y;
-        if p == a * x + b * y && (3 * a + b) < (3 * current_a + current_b) {
-            current_a = a;
-            current_b = b
-        }
+// s and t with s * x + t * y == gcd(x, y), the gcd never negative
+fn extended_gcd(x: i128, y: i128) -> (i128, i128, i128) {
+    if y == 0 {
+        return (x.abs(), x.signum(), 0);
+    }
+    let (g, s, t) = extended_gcd(y, x.rem_euclid(y));
+    (g, t, s - x.div_euclid(y) * t)
+}
+
+// narrows [low, high] to the k where value + k * step >= 0
+fn bound_non_negative(low: &mut i128, high: &mut i128, value: i128, step: i128) {
+    match step.signum() {
+        1 => *low = (*low).max(-value.div_euclid(step)),
+        -1 => *high = (*high).min(value.div_euclid(-step)),
+        _ if value < 0 => (*low, *high) = (1, 0),
+        _ => {}
+    }
+}
+
+// cheapest presses of (A, B), A costing 3 and B 1, with x * a + y * b == p
+// and neither above `max_presses` if there is a limit, or None if no mix of
+// presses gets there
+pub fn solve_linear(x: i128, y: i128, p: i128, max_presses: Option<i128>) -> Option<(i128, i128)> {
+    if x == 0 && y == 0 {
+        return (p == 0).then_some((0, 0));
+    }
+    let (g, s, t) = extended_gcd(x, y);
+    if p % g != 0 {
+        return None;
+    }
+    // every solution is (a0 + k * step_a, b0 - k * step_b)
+    let (a0, b0) = (s * (p / g), t * (p / g));
+    let (step_a, step_b) = (y / g, x / g);
+    let (mut low, mut high) = (i128::MIN, i128::MAX);
+    bound_non_negative(&mut low, &mut high, a0, step_a);
+    bound_non_negative(&mut low, &mut high, b0, -step_b);
+    if let Some(max) = max_presses {
+        bound_non_negative(&mut low, &mut high, max - a0, -step_a);
+        bound_non_negative(&mut low, &mut high, max - b0, step_b);
+    }
+    if low > high {
+        return None;
     }
-    (current_a, current_b)
+    // the cost changes by 3 * step_a - step_b per k, and can't fall below 0,
+    // so the bound on the side it falls towards is always a finite one
+    let k = match 3 * step_a - step_b > 0 || high == i128::MAX {
+        true => low,
+        false => high,
+    };
+    Some((a0 + k * step_a, b0 - k * step_b))
 }
 
 #[aoc(day13, part1)]
-pub fn part1(prizes: &Vec<Prize>) -> i128 {
+pub fn part1(prizes: &[Prize]) -> i128 {
     let mut tokens = 0;
     for p in prizes {
-        if let Some(t) = solve(p) {
-            dbg!(t);
-            tokens += t;
+        // part 1 only allows up to 100 presses per button
+        if let Some((a, b)) = solve(p, Some(100)) {
+            tokens += 3 * a + b;
         }
     }
     tokens
 }
 
 #[aoc(day13, part2)]
-pub fn part2(prizes: &Vec<Prize>) -> i128 {
+pub fn part2(prizes: &[Prize]) -> i128 {
     let mut tokens = 0;
     for p in prizes {
         let mut new_p = p.clone();
         new_p.coords.0 += 10000000000000;
         new_p.coords.1 += 10000000000000;
-        dbg!(&new_p);
-        if let Some(t) = solve(&new_p) {
-            tokens += t;
+        if let Some((a, b)) = solve(&new_p, None) {
+            tokens += 3 * a + b;
         }
     }
     tokens
@@ -1,7 +1,8 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use regex::Regex;
 use image::{ImageBuffer, Rgb};
 
+use crate::parse::{self, ParseError};
+
 #[derive(Debug, Clone, Copy)]
 pub struct Robot {
     x: i64,
@@ -37,22 +38,17 @@ impl Robot {
 }
 
 #[aoc_generator(day14)]
-pub fn input_generator(input: &str) -> Vec<Robot> {
+pub fn input_generator(input: &str) -> Result<Vec<Robot>, ParseError> {
     let mut robots = vec![];
-    let regex = Regex::new(r"p=(\d+),(\d+)\s+v=(-?\d+),(-?\d+)").unwrap();
     for line in input.lines() {
-        let captured = regex.captures(line).unwrap();
-        let x = captured[1].parse::<i64>().unwrap();
-        let y = captured[2].parse::<i64>().unwrap();
-        let v_x = captured[3].parse::<i64>().unwrap();
-        let v_y = captured[4].parse::<i64>().unwrap();
+        let [x, y, v_x, v_y] = parse::ints_exact(line)?;
         let robot = Robot { x, y, v_x, v_y };
         robots.push(robot);
     }
-    robots
+    Ok(robots)
 }
 
-pub fn safety_factor(robots: &Vec<Robot>) -> i64 {
+pub fn safety_factor(robots: &[Robot]) -> i64 {
     let (mut top_left, mut top_right, mut bottom_left, mut bottom_right) = (0, 0, 0, 0);
 
     let middle_col = (COLS - 1) / 2;
@@ -72,16 +68,15 @@ pub fn safety_factor(robots: &Vec<Robot>) -> i64 {
         // robots exactly on middle row or middle column are not counted
     }
 
-    let safety_factor = top_left * top_right * bottom_left * bottom_right;
-    safety_factor
+    top_left * top_right * bottom_left * bottom_right
 }
 
 const ROWS: i64 = 103;
 const COLS: i64 = 101;
 
 #[aoc(day14, part1)]
-pub fn part1(robots: &Vec<Robot>) -> i64 {
-    let mut moved_robots = robots.clone();
+pub fn part1(robots: &[Robot]) -> i64 {
+    let mut moved_robots = robots.to_vec();
     for robot in moved_robots.iter_mut() {
         robot.move_n(100);
     }
@@ -105,7 +100,7 @@ pub fn display_grid(robots: &[Robot]) {
 }
 
 pub fn render_grid_as_image(
-    robots: &Vec<Robot>,
+    robots: &[Robot],
     time_step: usize,
     file_path_prefix: &str,
 ) {
@@ -133,8 +128,8 @@ pub fn render_grid_as_image(
 }
 
 #[aoc(day14, part2)]
-pub fn part2_visualizer(initial_robots: &Vec<Robot>) -> String { // Or whatever return type AoC expects
-    let mut current_robots = initial_robots.clone();
+pub fn part2_visualizer(initial_robots: &[Robot]) -> String { // Or whatever return type AoC expects
+    let mut current_robots = initial_robots.to_vec();
 
     // Simulate for a certain number of steps, or until the pattern is found.
     // For AoC 2024 Day 14 Part 2, you might need to run for many thousands of steps.
@@ -151,9 +146,8 @@ pub fn part2_visualizer(initial_robots: &Vec<Robot>) -> String { // Or whatever
         // Render the grid at this time step
         // You might choose to render every N steps if rendering every step is too slow
         // or generates too many files.
-        if t % 1 == 0 { // Render every frame in this example
-            render_grid_as_image(&current_robots, t, "G:/repos/aoc-2024/output_frames/frame");
-        }
+        // Render every frame in this example
+        render_grid_as_image(&current_robots, t, "G:/repos/aoc-2024/output_frames/frame");
 
         // Here, you would also add logic to check if `current_robots`
         // form the "Christmas tree" pattern. The problem is typically
@@ -0,0 +1,112 @@
+use std::error::Error;
+
+use aoc_runner_derive::aoc;
+
+use crate::day6::Direction;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError};
+
+const WALL: u8 = b'#';
+const EMPTY: u8 = b'.';
+const ROBOT: u8 = b'@';
+const BOX: u8 = b'O';
+const BOX_LEFT: u8 = b'[';
+const BOX_RIGHT: u8 = b']';
+
+// the warehouse map and the robot's moves; the moves may be split over
+// several lines
+pub fn parse_moves(input: &str) -> Result<(&str, Vec<Direction>), Box<dyn Error>> {
+    let mut blocks = parse::blocks(input);
+    let map = blocks.next().ok_or(ParseError::Missing("warehouse map"))?;
+    let moves = blocks.next().ok_or(ParseError::Missing("move list"))?;
+    let moves = moves
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .map(|b| Direction::from_glyph(b).ok_or(format!("unknown move {:?}", b as char)))
+        .collect::<Result<_, _>>()?;
+    Ok((map, moves))
+}
+
+// moves the robot one step if nothing in the way is stuck against a wall.
+// Everything that gets pushed is collected first, wide boxes pulling in their
+// other half on vertical moves so the push spreads out like a tree, then all
+// of it moves at once. Off the map counts as a wall.
+fn push(grid: &mut Grid, robot: (usize, usize), dir: Direction) -> (usize, usize) {
+    let offset = dir.offset();
+    let mut moving = vec![robot];
+    let mut i = 0;
+    while i < moving.len() {
+        let Some(next) = grid.step(moving[i], offset) else {
+            return robot;
+        };
+        i += 1;
+        let partner = match grid[next] {
+            WALL => return robot,
+            EMPTY => continue,
+            BOX_LEFT if offset.0 != 0 => grid.step(next, (0, 1)).filter(|&p| grid[p] == BOX_RIGHT),
+            BOX_RIGHT if offset.0 != 0 => grid.step(next, (0, -1)).filter(|&p| grid[p] == BOX_LEFT),
+            _ => None,
+        };
+        for pos in [Some(next), partner].into_iter().flatten() {
+            if !moving.contains(&pos) {
+                moving.push(pos);
+            }
+        }
+    }
+
+    let cells: Vec<((usize, usize), u8)> = moving.iter().map(|&pos| (pos, grid[pos])).collect();
+    for &(pos, _) in &cells {
+        grid.set(pos, EMPTY);
+    }
+    for &(pos, cell) in &cells {
+        // every step was checked above
+        grid.set(grid.step(pos, offset).unwrap(), cell);
+    }
+    grid.step(robot, offset).unwrap()
+}
+
+fn simulate(map: &str, moves: &[Direction]) -> Result<usize, Box<dyn Error>> {
+    let mut grid = Grid::parse(map)?;
+    let mut robot = grid.find(ROBOT).ok_or(ParseError::Missing("robot '@'"))?;
+    if let Some((_, cell)) = grid
+        .iter()
+        .find(|&(_, cell)| ![WALL, EMPTY, ROBOT, BOX, BOX_LEFT, BOX_RIGHT].contains(&cell))
+    {
+        return Err(format!("unexpected {:?} on the map", cell as char).into());
+    }
+    for &dir in moves {
+        robot = push(&mut grid, robot, dir);
+    }
+    Ok(grid
+        .iter()
+        .filter(|&(_, cell)| cell == BOX || cell == BOX_LEFT)
+        .map(|((row, col), _)| 100 * row + col)
+        .sum())
+}
+
+// every cell twice as wide, boxes become "[]" and the robot keeps its left half
+pub fn widen(map: &str) -> String {
+    let mut wide = String::with_capacity(map.len() * 2);
+    for c in map.chars() {
+        match c {
+            '#' => wide.push_str("##"),
+            'O' => wide.push_str("[]"),
+            '.' => wide.push_str(".."),
+            '@' => wide.push_str("@."),
+            c => wide.push(c),
+        }
+    }
+    wide
+}
+
+#[aoc(day15, part1)]
+pub fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    let (map, moves) = parse_moves(input)?;
+    simulate(map, &moves)
+}
+
+#[aoc(day15, part2)]
+pub fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    let (map, moves) = parse_moves(input)?;
+    simulate(&widen(map), &moves)
+}
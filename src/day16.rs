@@ -0,0 +1,131 @@
+use std::{cmp::Reverse, collections::BinaryHeap, error::Error};
+
+use aoc_runner_derive::aoc;
+
+use crate::bitset::GridSet;
+use crate::day6::Direction;
+use crate::grid::Grid;
+use crate::parse::ParseError;
+
+const WALL: u8 = b'#';
+
+// in the order of `Direction as usize`, so the next one is a clockwise turn
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+];
+
+const STEP_COST: u64 = 1;
+const TURN_COST: u64 = 1000;
+
+// a state is a tile plus the way the reindeer faces, numbered like the
+// layers of a GridSet: (row * cols + col) * 4 + direction
+struct Search {
+    cols: usize,
+    cost: Vec<u64>,
+    // every state a cheapest path to this one can come from
+    predecessors: Vec<Vec<usize>>,
+    end: (usize, usize),
+}
+
+impl Search {
+    fn state(&self, (row, col): (usize, usize), dir: Direction) -> usize {
+        (row * self.cols + col) * 4 + dir as usize
+    }
+
+    fn position(&self, state: usize) -> (usize, usize) {
+        (state / 4 / self.cols, state / 4 % self.cols)
+    }
+
+    fn end_states(&self) -> [usize; 4] {
+        DIRECTIONS.map(|dir| self.state(self.end, dir))
+    }
+
+    fn best(&self) -> Option<u64> {
+        self.end_states()
+            .iter()
+            .map(|&state| self.cost[state])
+            .min()
+            .filter(|&cost| cost != u64::MAX)
+    }
+}
+
+// Dijkstra from S facing east, keeping every predecessor that ties for the
+// cheapest cost instead of just the first one found
+fn search(grid: &Grid) -> Result<Search, ParseError> {
+    let start = grid.find(b'S').ok_or(ParseError::Missing("start 'S'"))?;
+    let end = grid.find(b'E').ok_or(ParseError::Missing("end 'E'"))?;
+    let states = grid.rows() * grid.cols() * 4;
+    let mut search = Search {
+        cols: grid.cols(),
+        cost: vec![u64::MAX; states],
+        predecessors: vec![vec![]; states],
+        end,
+    };
+
+    let mut queue = BinaryHeap::new();
+    let first = search.state(start, Direction::Right);
+    search.cost[first] = 0;
+    queue.push(Reverse((0, first)));
+    while let Some(Reverse((cost, state))) = queue.pop() {
+        if cost > search.cost[state] {
+            continue;
+        }
+        let pos = search.position(state);
+        let dir = DIRECTIONS[state % 4];
+        let ahead = grid
+            .step(pos, dir.offset())
+            .filter(|&next| grid[next] != WALL);
+        let moves = [
+            ahead.map(|next| (search.state(next, dir), cost + STEP_COST)),
+            Some((search.state(pos, dir.next()), cost + TURN_COST)),
+            Some((
+                search.state(pos, dir.next().next().next()),
+                cost + TURN_COST,
+            )),
+        ];
+        for (next, next_cost) in moves.into_iter().flatten() {
+            if next_cost < search.cost[next] {
+                search.cost[next] = next_cost;
+                search.predecessors[next] = vec![state];
+                queue.push(Reverse((next_cost, next)));
+            } else if next_cost == search.cost[next] {
+                search.predecessors[next].push(state);
+            }
+        }
+    }
+    Ok(search)
+}
+
+#[aoc(day16, part1)]
+pub fn part1(input: &str) -> Result<u64, Box<dyn Error>> {
+    let grid = Grid::parse(input)?;
+    let search = search(&grid)?;
+    Ok(search.best().ok_or("no path from S to E")?)
+}
+
+// walks the predecessors back from every cheapest way of arriving at E
+#[aoc(day16, part2)]
+pub fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    let grid = Grid::parse(input)?;
+    let search = search(&grid)?;
+    let best = search.best().ok_or("no path from S to E")?;
+
+    let mut seen = GridSet::with_layers(grid.rows(), grid.cols(), 4);
+    let mut tiles = GridSet::new(grid.rows(), grid.cols());
+    let mut stack: Vec<usize> = search
+        .end_states()
+        .into_iter()
+        .filter(|&state| search.cost[state] == best)
+        .collect();
+    while let Some(state) = stack.pop() {
+        let pos = search.position(state);
+        if seen.insert_layer(pos, state % 4) {
+            tiles.insert(pos);
+            stack.extend(&search.predecessors[state]);
+        }
+    }
+    Ok(tiles.len())
+}
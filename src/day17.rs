@@ -0,0 +1,209 @@
+use std::fmt;
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::parse::{self, ParseError};
+
+#[derive(Debug, Clone)]
+pub struct Computer {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    // 3-bit numbers, opcodes and operands taking turns
+    pub program: Vec<u8>,
+}
+
+#[aoc_generator(day17)]
+pub fn input_generator(input: &str) -> Result<Computer, ParseError> {
+    let (mut a, mut b, mut c, mut program) = (None, None, None, None);
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let (key, value) = parse::key_value(line, ':')?;
+        match key {
+            "Register A" => a = Some(parse::number(value)?),
+            "Register B" => b = Some(parse::number(value)?),
+            "Register C" => c = Some(parse::number(value)?),
+            "Program" => {
+                let numbers: Vec<u8> = value
+                    .split(',')
+                    .map(parse::number)
+                    .collect::<Result<_, _>>()?;
+                if let Some(&n) = numbers.iter().find(|&&n| n > 7) {
+                    return Err(ParseError::InvalidNumber(n.to_string()));
+                }
+                program = Some(numbers);
+            }
+            _ => return Err(ParseError::Missing("register or program name")),
+        }
+    }
+    Ok(Computer {
+        a: a.ok_or(ParseError::Missing("register A"))?,
+        b: b.ok_or(ParseError::Missing("register B"))?,
+        c: c.ok_or(ParseError::Missing("register C"))?,
+        program: program.ok_or(ParseError::Missing("program"))?,
+    })
+}
+
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+// real programs halt after a few hundred instructions, this is for the ones
+// that jump back forever
+const MAX_STEPS: usize = 1_000_000;
+
+// the registers and instruction pointer while a program runs
+#[derive(Debug, Clone)]
+pub struct Vm<'a> {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    pub ip: usize,
+    program: &'a [u8],
+}
+
+impl Vm<'_> {
+    // opcode and operand at the instruction pointer, None once it has run
+    // off the end of the program, which halts it
+    pub fn instruction(&self) -> Option<(u8, u8)> {
+        Some((*self.program.get(self.ip)?, *self.program.get(self.ip + 1)?))
+    }
+
+    fn combo(&self, operand: u8) -> Result<u64, String> {
+        match operand {
+            0..=3 => Ok(operand as u64),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            _ => Err(format!("reserved combo operand 7 at {}", self.ip)),
+        }
+    }
+
+    // the dv instructions divide by 2^operand, a shift that runs every bit out
+    // past 63
+    fn divide(&self, operand: u8) -> Result<u64, String> {
+        let shift = u32::try_from(self.combo(operand)?).unwrap_or(u32::MAX);
+        Ok(self.a.checked_shr(shift).unwrap_or(0))
+    }
+
+    fn execute(&mut self, opcode: u8, operand: u8, out: &mut Vec<u8>) -> Result<(), String> {
+        match opcode {
+            0 => self.a = self.divide(operand)?,
+            1 => self.b ^= operand as u64,
+            2 => self.b = self.combo(operand)? % 8,
+            3 if self.a != 0 => {
+                self.ip = operand as usize;
+                return Ok(());
+            }
+            3 => {}
+            4 => self.b ^= self.c,
+            5 => out.push((self.combo(operand)? % 8) as u8),
+            6 => self.b = self.divide(operand)?,
+            _ => self.c = self.divide(operand)?,
+        }
+        self.ip += 2;
+        Ok(())
+    }
+}
+
+// one line of an instruction trace, the instruction about to run and the
+// registers before it does
+impl fmt::Display for Vm<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.instruction() {
+            Some((opcode, operand)) => write!(
+                f,
+                "{:>3}: {} {}  A={} B={} C={}",
+                self.ip, MNEMONICS[opcode as usize], operand, self.a, self.b, self.c
+            ),
+            None => write!(f, "{:>3}: halt", self.ip),
+        }
+    }
+}
+
+impl Computer {
+    pub fn run(&self, a: u64) -> Result<Vec<u8>, String> {
+        self.run_traced(a, |_| {})
+    }
+
+    // like `run`, calling `trace` before every instruction
+    pub fn run_traced(&self, a: u64, mut trace: impl FnMut(&Vm)) -> Result<Vec<u8>, String> {
+        let mut vm = Vm {
+            a,
+            b: self.b,
+            c: self.c,
+            ip: 0,
+            program: &self.program,
+        };
+        let mut out = vec![];
+        for _ in 0..MAX_STEPS {
+            let Some((opcode, operand)) = vm.instruction() else {
+                return Ok(out);
+            };
+            trace(&vm);
+            vm.execute(opcode, operand, &mut out)?;
+        }
+        Err(format!("still running after {} instructions", MAX_STEPS))
+    }
+
+    // every instruction the program runs with register A set to `a`
+    pub fn trace(&self, a: u64) -> Result<Vec<String>, String> {
+        let mut lines = vec![];
+        self.run_traced(a, |vm| lines.push(vm.to_string()))?;
+        Ok(lines)
+    }
+}
+
+fn join(out: &[u8]) -> String {
+    out.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[aoc(day17, part1)]
+pub fn part1(computer: &Computer) -> Result<String, String> {
+    Ok(join(&computer.run(computer.a)?))
+}
+
+// programs run more than this many times before the quine search gives up
+const MAX_SEARCH_RUNS: usize = 100_000;
+
+// the programs print one digit per loop and shift A right by 3 bits each
+// time round, so the last digit printed only depends on A's top octal digit,
+// the one before on the top two and so on. `prefix` holds the top digits
+// that already print program[index + 1..]; the next digit is tried in
+// increasing order so the first complete match is the smallest.
+fn find_quine(
+    computer: &Computer,
+    index: usize,
+    prefix: u64,
+    runs: &mut usize,
+) -> Result<Option<u64>, String> {
+    for digit in 0..8 {
+        let Some(a) = prefix.checked_mul(8).map(|a| a + digit) else {
+            return Ok(None);
+        };
+        *runs += 1;
+        if *runs > MAX_SEARCH_RUNS {
+            return Err(format!("no quine after {} runs", MAX_SEARCH_RUNS));
+        }
+        if computer.run(a)? != computer.program[index..] {
+            continue;
+        }
+        if index == 0 {
+            return Ok(Some(a));
+        }
+        if let Some(found) = find_quine(computer, index - 1, a, runs)? {
+            return Ok(Some(found));
+        }
+    }
+    Ok(None)
+}
+
+#[aoc(day17, part2)]
+pub fn part2(computer: &Computer) -> Result<u64, String> {
+    let last = computer
+        .program
+        .len()
+        .checked_sub(1)
+        .ok_or("empty program")?;
+    find_quine(computer, last, 0, &mut 0)?.ok_or("no A makes the program print itself".to_string())
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::bitset::GridSet;
+use crate::parse::{self, ParseError};
+
+// (x, y) of every falling byte, in the order they fall
+#[aoc_generator(day18)]
+pub fn input_generator(input: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse::ints_exact(line).map(|[x, y]| (x, y)))
+        .collect()
+}
+
+// the memory space is `size` cells square, part 1 looks at it after the
+// first `fallen` bytes have landed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Memory {
+    pub size: usize,
+    pub fallen: usize,
+}
+
+impl Memory {
+    pub const REAL: Memory = Memory {
+        size: 71,
+        fallen: 1024,
+    };
+    // the puzzle text's example is a smaller space with fewer bytes fallen
+    pub const EXAMPLE: Memory = Memory {
+        size: 7,
+        fallen: 12,
+    };
+}
+
+// the memory the #[aoc] wrappers use, set from the command line; 0 means
+// the real one
+static SIZE: AtomicUsize = AtomicUsize::new(0);
+static FALLEN: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_memory(memory: Memory) {
+    SIZE.store(memory.size, Ordering::Relaxed);
+    FALLEN.store(memory.fallen, Ordering::Relaxed);
+}
+
+pub fn memory() -> Memory {
+    match (SIZE.load(Ordering::Relaxed), FALLEN.load(Ordering::Relaxed)) {
+        (0, _) => Memory::REAL,
+        (size, fallen) => Memory { size, fallen },
+    }
+}
+
+fn check_bounds(size: usize, bytes: &[(usize, usize)]) -> Result<(), String> {
+    if size == 0 {
+        return Err("the memory space is empty".to_string());
+    }
+    match bytes.iter().find(|&&(x, y)| x.max(y) >= size) {
+        Some((x, y)) => Err(format!("byte {},{} is outside the memory space", x, y)),
+        None => Ok(()),
+    }
+}
+
+const NEIGHBOURS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+fn neighbours(size: usize, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+    NEIGHBOURS.into_iter().filter_map(move |(dx, dy)| {
+        let x = x.checked_add_signed(dx as isize)?;
+        let y = y.checked_add_signed(dy as isize)?;
+        (x < size && y < size).then_some((x, y))
+    })
+}
+
+// steps from the top left corner to the bottom right one, None if the bytes
+// in `blocked` cut it off
+pub fn shortest_exit(size: usize, blocked: &GridSet) -> Option<usize> {
+    let exit = (size - 1, size - 1);
+    if blocked.contains((0, 0)) || blocked.contains(exit) {
+        return None;
+    }
+    let mut visited = GridSet::new(size, size);
+    let mut queue = VecDeque::from([((0, 0), 0)]);
+    visited.insert((0, 0));
+    while let Some((pos, steps)) = queue.pop_front() {
+        if pos == exit {
+            return Some(steps);
+        }
+        for next in neighbours(size, pos) {
+            if !blocked.contains(next) && visited.insert(next) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+// steps to the exit once `memory.fallen` bytes have landed
+pub fn steps_after_fallen(bytes: &[(usize, usize)], memory: Memory) -> Result<usize, String> {
+    check_bounds(memory.size, bytes)?;
+    let mut blocked = GridSet::new(memory.size, memory.size);
+    for &(x, y) in bytes.iter().take(memory.fallen) {
+        blocked.insert((x, y));
+    }
+    shortest_exit(memory.size, &blocked).ok_or("the exit is cut off".to_string())
+}
+
+#[aoc(day18, part1)]
+pub fn part1(bytes: &[(usize, usize)]) -> Result<usize, String> {
+    steps_after_fallen(bytes, memory())
+}
+
+// union-find over the free cells, path halving and union by size
+struct Regions {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl Regions {
+    fn new(cells: usize) -> Self {
+        Regions {
+            parent: (0..cells).collect(),
+            size: vec![1; cells],
+        }
+    }
+
+    fn find(&mut self, mut cell: usize) -> usize {
+        while self.parent[cell] != cell {
+            self.parent[cell] = self.parent[self.parent[cell]];
+            cell = self.parent[cell];
+        }
+        cell
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
+
+// lets every byte fall, then takes them away again last first, joining each
+// freed cell to its free neighbours; the byte whose removal connects the
+// corners is the first one that cut them apart
+pub fn first_blocking(size: usize, bytes: &[(usize, usize)]) -> Option<(usize, usize)> {
+    let index = |(x, y): (usize, usize)| y * size + x;
+    let mut blocked = GridSet::new(size, size);
+    // a cell only frees up once the first byte that landed on it is gone
+    let mut first_landed = vec![usize::MAX; size * size];
+    for (i, &pos) in bytes.iter().enumerate() {
+        blocked.insert(pos);
+        first_landed[index(pos)] = first_landed[index(pos)].min(i);
+    }
+
+    // blocked cells never get joined to anything
+    let mut regions = Regions::new(size * size);
+    let join_free_neighbours = |regions: &mut Regions, blocked: &GridSet, pos| {
+        for next in neighbours(size, pos) {
+            if !blocked.contains(next) {
+                regions.union(index(pos), index(next));
+            }
+        }
+    };
+    for y in 0..size {
+        for x in 0..size {
+            if !blocked.contains((x, y)) {
+                join_free_neighbours(&mut regions, &blocked, (x, y));
+            }
+        }
+    }
+    let (start, exit) = (index((0, 0)), index((size - 1, size - 1)));
+    if regions.find(start) == regions.find(exit) {
+        return None;
+    }
+
+    for (i, &pos) in bytes.iter().enumerate().rev() {
+        if first_landed[index(pos)] != i {
+            continue;
+        }
+        blocked.remove(pos);
+        join_free_neighbours(&mut regions, &blocked, pos);
+        if regions.find(start) == regions.find(exit) {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+// "x,y" of the first byte that cuts the exit off in a `size` square space
+pub fn blocking_byte(bytes: &[(usize, usize)], size: usize) -> Result<String, String> {
+    check_bounds(size, bytes)?;
+    let (x, y) = first_blocking(size, bytes).ok_or("no byte cuts off the exit")?;
+    Ok(format!("{},{}", x, y))
+}
+
+#[aoc(day18, part2)]
+pub fn part2(bytes: &[(usize, usize)]) -> Result<String, String> {
+    blocking_byte(bytes, memory().size)
+}
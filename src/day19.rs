@@ -0,0 +1,80 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::hash::FastHashMap;
+use crate::parse::{self, ParseError};
+
+#[derive(Debug, Clone)]
+pub struct Onsen {
+    pub patterns: Vec<String>,
+    pub designs: Vec<String>,
+}
+
+#[aoc_generator(day19)]
+pub fn input_generator(input: &str) -> Result<Onsen, ParseError> {
+    let mut blocks = parse::blocks(input);
+    let patterns = blocks.next().ok_or(ParseError::Missing("towel patterns"))?;
+    let designs = blocks.next().ok_or(ParseError::Missing("designs"))?;
+    Ok(Onsen {
+        // an empty pattern would match forever without using up the design
+        patterns: patterns
+            .split(',')
+            .map(|pattern| pattern.trim().to_string())
+            .filter(|pattern| !pattern.is_empty())
+            .collect(),
+        designs: designs
+            .lines()
+            .map(|line| line.trim().to_string())
+            .collect(),
+    })
+}
+
+// ways to make `design` out of the patterns, None if that does not fit in a
+// u64; remembered by the part of the design that is left, so designs sharing
+// an ending reuse each other's counts
+fn count_arrangements<'a>(
+    design: &'a str,
+    patterns: &[String],
+    memo_cache: &mut FastHashMap<&'a str, Option<u64>>,
+) -> Option<u64> {
+    if design.is_empty() {
+        return Some(1);
+    }
+    if let Some(&cached) = memo_cache.get(design) {
+        return cached;
+    }
+    let mut ways: Option<u64> = Some(0);
+    for pattern in patterns {
+        if let Some(rest) = design.strip_prefix(pattern.as_str()) {
+            let rest_ways = count_arrangements(rest, patterns, memo_cache);
+            ways = ways.zip(rest_ways).and_then(|(a, b)| a.checked_add(b));
+        }
+    }
+    memo_cache.insert(design, ways);
+    ways
+}
+
+// per design, None where the count overflowed
+fn arrangements(onsen: &Onsen) -> Vec<Option<u64>> {
+    let mut memo_cache = FastHashMap::default();
+    onsen
+        .designs
+        .iter()
+        .map(|design| count_arrangements(design, &onsen.patterns, &mut memo_cache))
+        .collect()
+}
+
+#[aoc(day19, part1)]
+pub fn part1(onsen: &Onsen) -> usize {
+    arrangements(onsen)
+        .into_iter()
+        .filter(|&ways| ways != Some(0))
+        .count()
+}
+
+#[aoc(day19, part2)]
+pub fn part2(onsen: &Onsen) -> Result<u64, String> {
+    arrangements(onsen)
+        .into_iter()
+        .try_fold(0u64, |total, ways| total.checked_add(ways?))
+        .ok_or("too many arrangements to count".to_string())
+}
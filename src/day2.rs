@@ -1,25 +1,22 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::parse::{self, ParseError};
+
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<Vec<u32>> {
-    let mut lines = input.lines();
+pub fn input_generator(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
     let mut data: Vec<Vec<u32>> = Vec::new();
-    while let Some(line) = lines.next() {
-        data.push(
-            line.split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect(),
-        )
+    for line in input.lines() {
+        data.push(parse::ints(line)?)
     }
-    data
+    Ok(data)
 }
 
-fn is_safe(report: &Vec<u32>) -> bool {
+fn is_safe(report: &[u32]) -> bool {
     let mut diff = 0;
     for window in report.windows(2) {
         if let &[first, second] = window {
             let new_diff = second as i32 - first as i32;
-            if diff * new_diff < 0 || new_diff > 3 || new_diff < -3 || new_diff == 0 {
+            if diff * new_diff < 0 || !(-3..=3).contains(&new_diff) || new_diff == 0 {
                 return false;
             }
             diff = new_diff
@@ -28,7 +25,7 @@ fn is_safe(report: &Vec<u32>) -> bool {
     true
 }
 
-fn is_somewhat_safe(report: &Vec<u32>) -> bool {
+fn is_somewhat_safe(report: &[u32]) -> bool {
     let mut v: Vec<Vec<u32>> = Vec::with_capacity(report.len());
     for i in 0..report.len() {
         let cut_vec = report[..i]
@@ -47,7 +44,7 @@ fn is_somewhat_safe(report: &Vec<u32>) -> bool {
 }
 
 #[aoc(day2, part1)]
-pub fn part1(data: &Vec<Vec<u32>>) -> u32 {
+pub fn part1(data: &[Vec<u32>]) -> u32 {
     let mut safe_count = 0u32;
     for report in data {
         if is_safe(report) || is_somewhat_safe(report) {
@@ -0,0 +1,133 @@
+use std::{
+    collections::VecDeque,
+    error::Error,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use aoc_runner_derive::aoc;
+
+use crate::grid::Grid;
+use crate::parallel;
+use crate::parse::ParseError;
+
+const WALL: u8 = b'#';
+
+// how much time a cheat has to save to be counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thresholds {
+    pub part1: usize,
+    pub part2: usize,
+}
+
+impl Thresholds {
+    pub const REAL: Thresholds = Thresholds {
+        part1: 100,
+        part2: 100,
+    };
+    // no cheat on the puzzle's example saves 100, its text adds up all the
+    // cheats for part 1 and the ones saving at least 50 for part 2
+    pub const EXAMPLE: Thresholds = Thresholds {
+        part1: 1,
+        part2: 50,
+    };
+}
+
+// the thresholds the #[aoc] wrappers use, set from the command line; the
+// real ones until then, a threshold of 0 counts every cheat
+static SET: AtomicBool = AtomicBool::new(false);
+static PART1: AtomicUsize = AtomicUsize::new(0);
+static PART2: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_thresholds(thresholds: Thresholds) {
+    PART1.store(thresholds.part1, Ordering::Relaxed);
+    PART2.store(thresholds.part2, Ordering::Relaxed);
+    SET.store(true, Ordering::Relaxed);
+}
+
+pub fn thresholds() -> Thresholds {
+    if !SET.load(Ordering::Relaxed) {
+        return Thresholds::REAL;
+    }
+    Thresholds {
+        part1: PART1.load(Ordering::Relaxed),
+        part2: PART2.load(Ordering::Relaxed),
+    }
+}
+
+// picoseconds from S to every cell on the track, walls and cells off the
+// track stay at usize::MAX; indexed by row * cols + col
+pub fn track_distances(grid: &Grid) -> Result<Vec<usize>, Box<dyn Error>> {
+    let start = grid.find(b'S').ok_or(ParseError::Missing("start 'S'"))?;
+    let end = grid.find(b'E').ok_or(ParseError::Missing("end 'E'"))?;
+    let index = |(row, col): (usize, usize)| row * grid.cols() + col;
+    let mut distance = vec![usize::MAX; grid.rows() * grid.cols()];
+    distance[index(start)] = 0;
+    let mut queue = VecDeque::from([start]);
+    while let Some(pos) = queue.pop_front() {
+        for offset in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            if let Some(next) = grid.step(pos, offset)
+                && grid[next] != WALL
+                && distance[index(next)] == usize::MAX
+            {
+                distance[index(next)] = distance[index(pos)] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    if distance[index(end)] == usize::MAX {
+        return Err("no track from S to E".into());
+    }
+    Ok(distance)
+}
+
+// cheats of at most `max_len` picoseconds that save at least `min_saving`.
+// A cheat goes from one track cell to any other within `max_len` steps of
+// Manhattan distance, walls or not, so every cell in that diamond around
+// each track cell is checked rather than searching for paths through walls.
+pub fn count_cheats(grid: &Grid, distance: &[usize], max_len: usize, min_saving: usize) -> usize {
+    let track: Vec<(usize, usize)> = grid
+        .positions()
+        .filter(|&(row, col)| distance[row * grid.cols() + col] != usize::MAX)
+        .collect();
+    let radius = max_len as isize;
+    let counts = parallel::map(&track, |&(row, col)| {
+        let from = distance[row * grid.cols() + col];
+        let mut count = 0;
+        for dr in -radius..=radius {
+            let reach = radius - dr.abs();
+            for dc in -reach..=reach {
+                let Some(to) = grid.step((row, col), (dr as i32, dc as i32)) else {
+                    continue;
+                };
+                let to = distance[to.0 * grid.cols() + to.1];
+                let len = dr.unsigned_abs() + dc.unsigned_abs();
+                if to != usize::MAX && to >= from + len + min_saving {
+                    count += 1;
+                }
+            }
+        }
+        count
+    });
+    counts.into_iter().sum()
+}
+
+// count_cheats on the race track in `input`
+pub fn track_cheats(
+    input: &str,
+    max_len: usize,
+    min_saving: usize,
+) -> Result<usize, Box<dyn Error>> {
+    let grid = Grid::parse(input)?;
+    let distance = track_distances(&grid)?;
+    Ok(count_cheats(&grid, &distance, max_len, min_saving))
+}
+
+#[aoc(day20, part1)]
+pub fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    track_cheats(input, 2, thresholds().part1)
+}
+
+#[aoc(day20, part2)]
+pub fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    track_cheats(input, 20, thresholds().part2)
+}
@@ -0,0 +1,152 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::hash::FastHashMap;
+use crate::parse::{self, ParseError};
+
+#[aoc_generator(day21)]
+pub fn input_generator(input: &str) -> Result<Vec<String>, ParseError> {
+    let mut codes = vec![];
+    for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if line.bytes().any(|key| NUMERIC.position(key).is_none()) {
+            return Err(ParseError::InvalidNumber(line.to_string()));
+        }
+        codes.push(line.to_string());
+    }
+    Ok(codes)
+}
+
+// rows of keys, b' ' for the gap no robot arm may point at
+pub struct Keypad {
+    rows: &'static [&'static [u8]],
+}
+
+pub const NUMERIC: Keypad = Keypad {
+    rows: &[b"789", b"456", b"123", b" 0A"],
+};
+
+pub const DIRECTIONAL: Keypad = Keypad {
+    rows: &[b" ^A", b"<v>"],
+};
+
+impl Keypad {
+    fn position(&self, key: u8) -> Option<(usize, usize)> {
+        if key == b' ' {
+            return None;
+        }
+        self.rows.iter().enumerate().find_map(|(row, keys)| {
+            let col = keys.iter().position(|&k| k == key)?;
+            Some((row, col))
+        })
+    }
+
+    // the ways worth trying to get from `from` to `to` and press it: all the
+    // horizontal moves then all the vertical ones, or the other way round,
+    // unless that passes over the gap. Zigzagging never helps, every change of
+    // direction costs the robot above another trip across its keypad.
+    fn paths(&self, from: u8, to: u8) -> Vec<Vec<u8>> {
+        let (Some(from), Some(to)) = (self.position(from), self.position(to)) else {
+            return vec![];
+        };
+        let vertical = match to.0 > from.0 {
+            true => vec![b'v'; to.0 - from.0],
+            false => vec![b'^'; from.0 - to.0],
+        };
+        let horizontal = match to.1 > from.1 {
+            true => vec![b'>'; to.1 - from.1],
+            false => vec![b'<'; from.1 - to.1],
+        };
+        let mut paths = vec![];
+        if self.rows[from.0][to.1] != b' ' {
+            paths.push([&horizontal[..], &vertical, b"A"].concat());
+        }
+        if self.rows[to.0][from.1] != b' ' {
+            paths.push([&vertical[..], &horizontal, b"A"].concat());
+        }
+        paths.dedup();
+        paths
+    }
+}
+
+// fewest presses for the person at the far end to have a robot type `keys`
+// on a directional keypad, with `layers` directional keypads between the
+// person and that robot; remembered per (from, to, layers) move
+fn directional_presses(
+    keys: &[u8],
+    layers: usize,
+    memo_cache: &mut FastHashMap<(u8, u8, usize), u64>,
+) -> u64 {
+    if layers == 0 {
+        return keys.len() as u64;
+    }
+    let mut presses = 0;
+    let mut from = b'A';
+    for &to in keys {
+        let key = (from, to, layers);
+        let cost = match memo_cache.get(&key) {
+            Some(&cached) => cached,
+            None => {
+                let cost = DIRECTIONAL
+                    .paths(from, to)
+                    .iter()
+                    .map(|path| directional_presses(path, layers - 1, memo_cache))
+                    .min()
+                    .unwrap_or(u64::MAX);
+                memo_cache.insert(key, cost);
+                cost
+            }
+        };
+        presses = cost.saturating_add(presses);
+        from = to;
+    }
+    presses
+}
+
+// fewest presses to type `code` on the door's numeric keypad through
+// `layers` robots on directional keypads; the sums saturate at u64::MAX so
+// total_complexity can reject an answer that does not fit
+pub fn code_presses(
+    code: &str,
+    layers: usize,
+    memo_cache: &mut FastHashMap<(u8, u8, usize), u64>,
+) -> u64 {
+    let mut presses = 0;
+    let mut from = b'A';
+    for to in code.bytes() {
+        let cost = NUMERIC
+            .paths(from, to)
+            .iter()
+            .map(|path| directional_presses(path, layers, memo_cache))
+            .min()
+            .unwrap_or(u64::MAX);
+        presses = cost.saturating_add(presses);
+        from = to;
+    }
+    presses
+}
+
+pub fn total_complexity(codes: &[String], layers: usize) -> Result<u64, String> {
+    let mut memo_cache = FastHashMap::default();
+    let mut total: u64 = 0;
+    for code in codes {
+        let digits: String = code.chars().filter(char::is_ascii_digit).collect();
+        let value = match digits.is_empty() {
+            true => 0,
+            false => parse::number::<u64>(&digits).map_err(|e| e.to_string())?,
+        };
+        total = code_presses(code, layers, &mut memo_cache)
+            .checked_mul(value)
+            .and_then(|complexity| total.checked_add(complexity))
+            .ok_or(format!("complexity of {} does not fit in a u64", code))?;
+    }
+    Ok(total)
+}
+
+#[aoc(day21, part1)]
+pub fn part1(codes: &[String]) -> Result<u64, String> {
+    total_complexity(codes, 2)
+}
+
+#[aoc(day21, part2)]
+pub fn part2(codes: &[String]) -> Result<u64, String> {
+    total_complexity(codes, 25)
+}
@@ -0,0 +1,68 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::parse::{self, ParseError};
+
+#[aoc_generator(day22)]
+pub fn input_generator(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse::number)
+        .collect()
+}
+
+const STEPS: usize = 2000;
+
+// secrets are kept modulo 2^24, so pruning is masking the low 24 bits; the
+// shifts can drop high bits of an oversized first secret without changing
+// the bits that are kept
+const PRUNE_MASK: u64 = (1 << 24) - 1;
+
+pub fn next_secret(secret: u64) -> u64 {
+    let secret = ((secret << 6) ^ secret) & PRUNE_MASK;
+    let secret = ((secret >> 5) ^ secret) & PRUNE_MASK;
+    ((secret << 11) ^ secret) & PRUNE_MASK
+}
+
+#[aoc(day22, part1)]
+pub fn part1(secrets: &[u64]) -> u64 {
+    secrets
+        .iter()
+        .map(|&secret| (0..STEPS).fold(secret, |secret, _| next_secret(secret)))
+        .sum()
+}
+
+// a price change is -9 to 9, so four in a row fit in one base-19 number
+const CHANGE_VALUES: usize = 19;
+const WINDOWS: usize = CHANGE_VALUES.pow(4);
+
+// bananas from every buyer for each window of four price changes, each
+// buyer selling at the first time the window shows up in their prices
+pub fn window_totals(secrets: &[u64]) -> Vec<u64> {
+    let mut totals = vec![0; WINDOWS];
+    // the last buyer that has sold on each window, so nothing needs clearing
+    // between buyers
+    let mut sold_by = vec![usize::MAX; WINDOWS];
+    for (buyer, &secret) in secrets.iter().enumerate() {
+        let mut secret = secret;
+        let mut price = secret % 10;
+        let mut window = 0;
+        for step in 0..STEPS {
+            secret = next_secret(secret);
+            let next_price = secret % 10;
+            let change = (next_price + 9 - price) as usize;
+            window = (window * CHANGE_VALUES + change) % WINDOWS;
+            price = next_price;
+            if step >= 3 && sold_by[window] != buyer {
+                sold_by[window] = buyer;
+                totals[window] += price;
+            }
+        }
+    }
+    totals
+}
+
+#[aoc(day22, part2)]
+pub fn part2(secrets: &[u64]) -> u64 {
+    window_totals(secrets).into_iter().max().unwrap_or(0)
+}
@@ -0,0 +1,122 @@
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::hash::{FastHashMap, FastHashSet};
+use crate::parse::{self, ParseError};
+
+// computers are numbered in the order they first show up
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    pub names: Vec<String>,
+    pub links: Vec<FastHashSet<usize>>,
+}
+
+#[aoc_generator(day23)]
+pub fn input_generator(input: &str) -> Result<Network, ParseError> {
+    let mut network = Network::default();
+    let mut ids: FastHashMap<String, usize> = FastHashMap::default();
+    let mut id = |network: &mut Network, name: &str| {
+        *ids.entry(name.to_string()).or_insert_with(|| {
+            network.names.push(name.to_string());
+            network.links.push(FastHashSet::default());
+            network.names.len() - 1
+        })
+    };
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let (a, b) = parse::key_value(line, '-')?;
+        let (a, b) = (id(&mut network, a), id(&mut network, b));
+        // a computer linked to itself adds nothing to a clique
+        if a != b {
+            network.links[a].insert(b);
+            network.links[b].insert(a);
+        }
+    }
+    Ok(network)
+}
+
+#[aoc(day23, part1)]
+pub fn part1(network: &Network) -> usize {
+    let historian = |c: usize| network.names[c].starts_with('t');
+    let mut triangles = 0;
+    // each triangle is counted once, from its lowest numbered computer
+    for (a, links) in network.links.iter().enumerate() {
+        for &b in links.iter().filter(|&&b| b > a) {
+            for &c in links.iter().filter(|&&c| c > b) {
+                if network.links[b].contains(&c) && (historian(a) || historian(b) || historian(c)) {
+                    triangles += 1;
+                }
+            }
+        }
+    }
+    triangles
+}
+
+// Bron–Kerbosch with pivoting: `clique` can still grow by anything in
+// `candidates`, everything in `excluded` was tried already. Only candidates
+// not linked to the pivot are branched on, since any clique with the pivot's
+// neighbours alone could also take the pivot or one of its non-neighbours.
+fn largest_clique(
+    network: &Network,
+    clique: &mut Vec<usize>,
+    mut candidates: FastHashSet<usize>,
+    mut excluded: FastHashSet<usize>,
+    best: &mut Vec<usize>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() && clique.len() > best.len() {
+            *best = clique.clone();
+        }
+        return;
+    }
+    // nothing found down here can beat the best clique so far
+    if clique.len() + candidates.len() <= best.len() {
+        return;
+    }
+    let pivot = candidates
+        .iter()
+        .chain(&excluded)
+        .copied()
+        .max_by_key(|&u| {
+            candidates
+                .iter()
+                .filter(|v| network.links[u].contains(v))
+                .count()
+        })
+        .unwrap();
+    let branches: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|v| !network.links[pivot].contains(v))
+        .collect();
+    for v in branches {
+        let links = &network.links[v];
+        clique.push(v);
+        largest_clique(
+            network,
+            clique,
+            candidates.intersection(links).copied().collect(),
+            excluded.intersection(links).copied().collect(),
+            best,
+        );
+        clique.pop();
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}
+
+// the LAN party's password, the names in its largest clique sorted and
+// joined with commas
+#[aoc(day23, part2)]
+pub fn part2(network: &Network) -> String {
+    let mut best = vec![];
+    let everyone = (0..network.names.len()).collect();
+    largest_clique(
+        network,
+        &mut vec![],
+        everyone,
+        FastHashSet::default(),
+        &mut best,
+    );
+    let mut names: Vec<&str> = best.iter().map(|&c| network.names[c].as_str()).collect();
+    names.sort();
+    names.join(",")
+}
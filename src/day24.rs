@@ -0,0 +1,222 @@
+use std::{collections::BTreeSet, error::Error, fmt, fmt::Write};
+
+use aoc_runner_derive::{aoc, aoc_generator};
+
+use crate::hash::FastHashMap;
+use crate::parse::{self, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Op::And => write!(f, "AND"),
+            Op::Or => write!(f, "OR"),
+            Op::Xor => write!(f, "XOR"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Gate {
+    pub a: String,
+    pub op: Op,
+    pub b: String,
+    pub out: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    pub inputs: Vec<(String, bool)>,
+    pub gates: Vec<Gate>,
+}
+
+fn wire_name(name: &str) -> Result<String, String> {
+    match !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        true => Ok(name.to_string()),
+        false => Err(format!("bad wire name {:?}", name)),
+    }
+}
+
+#[aoc_generator(day24)]
+pub fn input_generator(input: &str) -> Result<Circuit, Box<dyn Error>> {
+    let mut blocks = parse::blocks(input);
+    let inputs = blocks.next().ok_or(ParseError::Missing("input wires"))?;
+    let gates = blocks.next().ok_or(ParseError::Missing("gates"))?;
+    let mut circuit = Circuit {
+        inputs: vec![],
+        gates: vec![],
+    };
+    for line in inputs.lines() {
+        let (wire, value) = parse::key_value(line, ':')?;
+        let value = match value {
+            "0" => false,
+            "1" => true,
+            _ => return Err(ParseError::InvalidNumber(value.to_string()).into()),
+        };
+        circuit.inputs.push((wire_name(wire)?, value));
+    }
+    for line in gates.lines() {
+        let (lhs, out) = line
+            .split_once("->")
+            .ok_or(format!("no -> in {:?}", line))?;
+        let [a, op, b] = lhs.split_whitespace().collect::<Vec<_>>()[..] else {
+            return Err(format!("expected `wire OP wire` in {:?}", line).into());
+        };
+        let op = match op {
+            "AND" => Op::And,
+            "OR" => Op::Or,
+            "XOR" => Op::Xor,
+            _ => return Err(format!("unknown gate {:?}", op).into()),
+        };
+        circuit.gates.push(Gate {
+            a: wire_name(a)?,
+            op,
+            b: wire_name(b)?,
+            out: wire_name(out.trim())?,
+        });
+    }
+    Ok(circuit)
+}
+
+// the value on every wire once all the gates have settled; gates are
+// retried in passes until each has both its inputs
+pub fn evaluate(circuit: &Circuit) -> Result<FastHashMap<&str, bool>, String> {
+    let mut values: FastHashMap<&str, bool> = circuit
+        .inputs
+        .iter()
+        .map(|(wire, value)| (wire.as_str(), *value))
+        .collect();
+    let mut pending: Vec<&Gate> = circuit.gates.iter().collect();
+    while !pending.is_empty() {
+        let before = pending.len();
+        pending.retain(|gate| {
+            let (Some(&a), Some(&b)) = (values.get(gate.a.as_str()), values.get(gate.b.as_str()))
+            else {
+                return true;
+            };
+            let value = match gate.op {
+                Op::And => a && b,
+                Op::Or => a || b,
+                Op::Xor => a ^ b,
+            };
+            values.insert(&gate.out, value);
+            false
+        });
+        if pending.len() == before {
+            return Err(format!(
+                "{} gates wait on wires that never get a value",
+                pending.len()
+            ));
+        }
+    }
+    Ok(values)
+}
+
+#[aoc(day24, part1)]
+pub fn part1(circuit: &Circuit) -> Result<u64, String> {
+    let mut number = 0u64;
+    for (wire, value) in evaluate(circuit)? {
+        let Some(bit) = wire.strip_prefix('z') else {
+            continue;
+        };
+        let bit: u32 = parse::number(bit).map_err(|e| e.to_string())?;
+        let bit = 1u64
+            .checked_shl(bit)
+            .ok_or(format!("{} does not fit in a u64", wire))?;
+        if value {
+            number |= bit;
+        }
+    }
+    Ok(number)
+}
+
+fn is_input(wire: &str) -> bool {
+    wire.starts_with('x') || wire.starts_with('y')
+}
+
+fn is_first_bit(gate: &Gate) -> bool {
+    [&gate.a, &gate.b]
+        .iter()
+        .any(|wire| *wire == "x00" || *wire == "y00")
+}
+
+// output wires that break the shape of a ripple-carry adder. Bit i of a
+// correct one is
+//   x_i XOR y_i -> p_i        x_i AND y_i -> g_i
+//   p_i XOR c_i-1 -> z_i      p_i AND c_i-1 -> t_i      g_i OR t_i -> c_i
+// with the last carry as the top z bit, and bit 0 a half adder without c.
+// So only XORs write z wires (bar the top one, an OR), XORs not fed by x and
+// y write z wires, p goes on to an XOR and g and t go on to an OR.
+pub fn find_swapped(circuit: &Circuit) -> BTreeSet<&str> {
+    let last_z = circuit
+        .gates
+        .iter()
+        .map(|gate| gate.out.as_str())
+        .filter(|out| out.starts_with('z'))
+        .max()
+        .unwrap_or_default();
+    let feeds = |wire: &str, op: Op| {
+        circuit
+            .gates
+            .iter()
+            .any(|gate| gate.op == op && (gate.a == wire || gate.b == wire))
+    };
+
+    let mut swapped = BTreeSet::new();
+    for gate in &circuit.gates {
+        let out = gate.out.as_str();
+        let from_inputs = is_input(&gate.a) && is_input(&gate.b);
+        let wrong = match gate.op {
+            _ if out == last_z => gate.op != Op::Or,
+            Op::Xor if from_inputs => {
+                out.starts_with('z') != is_first_bit(gate)
+                    || (!is_first_bit(gate) && !feeds(out, Op::Xor))
+            }
+            Op::Xor => !out.starts_with('z'),
+            Op::And => out.starts_with('z') || (!is_first_bit(gate) && !feeds(out, Op::Or)),
+            Op::Or => out.starts_with('z'),
+        };
+        if wrong {
+            swapped.insert(out);
+        }
+    }
+    swapped
+}
+
+#[aoc(day24, part2)]
+pub fn part2(circuit: &Circuit) -> Result<String, String> {
+    let swapped: Vec<&str> = find_swapped(circuit).into_iter().collect();
+    match swapped.len() {
+        8 => Ok(swapped.join(",")),
+        found => Err(format!(
+            "expected 4 swapped pairs, {} wires look wrong: {}",
+            found,
+            swapped.join(",")
+        )),
+    }
+}
+
+// the circuit as a Graphviz graph, wires as ellipses and gates as boxes,
+// with the `highlight`ed wires filled in red
+pub fn to_dot(circuit: &Circuit, highlight: &BTreeSet<&str>) -> String {
+    let mut dot = String::from("digraph circuit {\n    rankdir=LR;\n");
+    for (i, gate) in circuit.gates.iter().enumerate() {
+        // wire names are alphanumeric, so the space keeps gates apart from them
+        let id = format!("\"gate {}\"", i);
+        writeln!(dot, "    {} [label=\"{}\", shape=box];", id, gate.op).unwrap();
+        writeln!(dot, "    \"{}\" -> {};", gate.a, id).unwrap();
+        writeln!(dot, "    \"{}\" -> {};", gate.b, id).unwrap();
+        writeln!(dot, "    {} -> \"{}\";", id, gate.out).unwrap();
+    }
+    for wire in highlight {
+        writeln!(dot, "    \"{}\" [style=filled, fillcolor=red];", wire).unwrap();
+    }
+    dot.push_str("}\n");
+    dot
+}
@@ -6,7 +6,7 @@ pub fn part1(input: &str) -> u32 {
     let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
     let mut sum = 0;
     for captured in re.captures_iter(input) {
-        sum += &captured[1].parse::<u32>().unwrap() * &captured[2].parse().unwrap();
+        sum += captured[1].parse::<u32>().unwrap() * captured[2].parse::<u32>().unwrap();
     }
     sum
 }
@@ -17,12 +17,12 @@ pub fn part2(input: &str) -> u32 {
     let mut enabled = true;
     let mut sum = 0;
     for captured in re.captures_iter(input) {
-        if let Some(_) = captured.get(3) {
+        if captured.get(3).is_some() {
             enabled = true;
-        } else if let Some(_) = captured.get(4) {
+        } else if captured.get(4).is_some() {
             enabled = false;
         } else if enabled {
-            sum += &captured[1].parse::<u32>().unwrap() * &captured[2].parse().unwrap();
+            sum += captured[1].parse::<u32>().unwrap() * captured[2].parse::<u32>().unwrap();
         }
     }
     sum
@@ -1,35 +1,25 @@
 use std::error::Error;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_runner_derive::aoc;
 
-#[aoc_generator(day4)]
-pub fn grid_generator(input: &str) -> Vec<Vec<char>> {
-    let mut grid: Vec<Vec<char>> = Vec::new();
-    for line in input.lines() {
-        grid.push(line.chars().collect::<Vec<char>>());
-    }
-    grid
-}
+use crate::grid::Grid;
+use crate::parse::ParseError;
 
-pub fn find_x(grid: &Vec<Vec<char>>) -> Vec<(usize, usize)> {
+pub fn find_x(grid: &Grid) -> Vec<(usize, usize)> {
     let mut x_pos: Vec<(usize, usize)> = Vec::new();
-    for row in 0..grid.len() {
-        for col in 0..grid[0].len() {
-            if grid[row][col] == 'X' {
-                x_pos.push((row, col));
-            }
+    for (pos, cell) in grid.iter() {
+        if cell == b'X' {
+            x_pos.push(pos);
         }
     }
     x_pos
 }
 
-pub fn find_a(grid: &Vec<Vec<char>>) -> Vec<(usize, usize)> {
+pub fn find_a(grid: &Grid) -> Vec<(usize, usize)> {
     let mut x_pos: Vec<(usize, usize)> = Vec::new();
-    for row in 0..grid.len() {
-        for col in 0..grid[0].len() {
-            if grid[row][col] == 'A' {
-                x_pos.push((row, col));
-            }
+    for (pos, cell) in grid.iter() {
+        if cell == b'A' {
+            x_pos.push(pos);
         }
     }
     x_pos
@@ -75,9 +65,9 @@ impl Direction {
     }
 }
 
-pub fn is_xmas(grid: &Vec<Vec<char>>, pos: (usize, usize), dir: Direction) -> bool {
+pub fn is_xmas(grid: &Grid, pos: (usize, usize), dir: Direction) -> bool {
     let offset = dir.offset();
-    let access = || -> Result<(char, char, char), Box<dyn Error>> {
+    let access = || -> Result<(u8, u8, u8), Box<dyn Error>> {
         // Convert position to i32 for calculation
         let row = pos.0 as i32;
         let col = pos.1 as i32;
@@ -97,31 +87,27 @@ pub fn is_xmas(grid: &Vec<Vec<char>>, pos: (usize, usize), dir: Direction) -> bo
             || col3 < 0
             || row4 < 0
             || col4 < 0
-            || row2 >= grid.len() as i32
-            || col2 >= grid[0].len() as i32
-            || row3 >= grid.len() as i32
-            || col3 >= grid[0].len() as i32
-            || row4 >= grid.len() as i32
-            || col4 >= grid[0].len() as i32
+            || row2 >= grid.rows() as i32
+            || col2 >= grid.cols() as i32
+            || row3 >= grid.rows() as i32
+            || col3 >= grid.cols() as i32
+            || row4 >= grid.rows() as i32
+            || col4 >= grid.cols() as i32
         {
             return Err("Out of bounds".into());
         }
 
         // Convert back to usize for indexing
-        let second = grid[row2 as usize][col2 as usize];
-        let third = grid[row3 as usize][col3 as usize];
-        let fourth = grid[row4 as usize][col4 as usize];
+        let second = grid[(row2 as usize, col2 as usize)];
+        let third = grid[(row3 as usize, col3 as usize)];
+        let fourth = grid[(row4 as usize, col4 as usize)];
 
         Ok((second, third, fourth))
     };
-    if let Ok(('M', 'A', 'S')) = access() {
-        return true;
-    } else {
-        false
-    }
+    matches!(access(), Ok((b'M', b'A', b'S')))
 }
 
-pub fn is_x_mas2(grid: &Vec<Vec<char>>, pos: (usize, usize)) -> u32 {
+pub fn is_x_mas2(grid: &Grid, pos: (usize, usize)) -> u32 {
     let (row, col) = (pos.0 as i32, pos.1 as i32);
     let (row_tl, col_tl) = (row - 1, col - 1);
     let (row_tr, col_tr) = (row - 1, col + 1);
@@ -137,26 +123,26 @@ pub fn is_x_mas2(grid: &Vec<Vec<char>>, pos: (usize, usize)) -> u32 {
         && col_bl >= 0
         && row_br >= 0
         && col_br >= 0
-        && row_tl < grid.len() as i32
-        && col_tl < grid[0].len() as i32
-        && row_tr < grid.len() as i32
-        && col_tr < grid[0].len() as i32
-        && row_bl < grid.len() as i32
-        && col_bl < grid[0].len() as i32
-        && row_br < grid.len() as i32
-        && col_br < grid[0].len() as i32
+        && row_tl < grid.rows() as i32
+        && col_tl < grid.cols() as i32
+        && row_tr < grid.rows() as i32
+        && col_tr < grid.cols() as i32
+        && row_bl < grid.rows() as i32
+        && col_bl < grid.cols() as i32
+        && row_br < grid.rows() as i32
+        && col_br < grid.cols() as i32
     {
         // Now it's safe to access these positions
-        let char_tl = grid[row_tl as usize][col_tl as usize];
-        let char_tr = grid[row_tr as usize][col_tr as usize];
-        let char_bl = grid[row_bl as usize][col_bl as usize];
-        let char_br = grid[row_br as usize][col_br as usize];
+        let char_tl = grid[(row_tl as usize, col_tl as usize)];
+        let char_tr = grid[(row_tr as usize, col_tr as usize)];
+        let char_bl = grid[(row_bl as usize, col_bl as usize)];
+        let char_br = grid[(row_br as usize, col_br as usize)];
 
         // Add your pattern checking logic here
         let first_pair =
-            ((char_tl == 'M') && (char_br == 'S')) || ((char_tl == 'S') && (char_br == 'M'));
+            ((char_tl == b'M') && (char_br == b'S')) || ((char_tl == b'S') && (char_br == b'M'));
         let second_pair =
-            ((char_tr == 'M') && (char_bl == 'S')) || ((char_tr == 'S') && (char_bl == 'M'));
+            ((char_tr == b'M') && (char_bl == b'S')) || ((char_tr == b'S') && (char_bl == b'M'));
         if first_pair && second_pair { 1 } else { 0 }
     } else {
         // Out of bounds, handle appropriately
@@ -164,7 +150,7 @@ pub fn is_x_mas2(grid: &Vec<Vec<char>>, pos: (usize, usize)) -> u32 {
     }
 }
 
-pub fn count_xmas(grid: &Vec<Vec<char>>, pos: (usize, usize)) -> u32 {
+pub fn count_xmas(grid: &Grid, pos: (usize, usize)) -> u32 {
     let mut count = 0;
     for direction in Direction::all() {
         if is_xmas(grid, pos, direction) {
@@ -175,21 +161,23 @@ pub fn count_xmas(grid: &Vec<Vec<char>>, pos: (usize, usize)) -> u32 {
 }
 
 #[aoc(day4, part1)]
-pub fn part1(grid: &Vec<Vec<char>>) -> u32 {
+pub fn part1(input: &str) -> Result<u32, ParseError> {
+    let grid = &Grid::parse(input)?;
     let x_pos = find_x(grid);
     let mut count = 0;
     for pos in x_pos {
         count += count_xmas(grid, pos);
     }
-    count
+    Ok(count)
 }
 
 #[aoc(day4, part2)]
-pub fn part2(grid: &Vec<Vec<char>>) -> u32 {
+pub fn part2(input: &str) -> Result<u32, ParseError> {
+    let grid = &Grid::parse(input)?;
     let a_pos = find_a(grid);
     let mut count = 0;
     for pos in a_pos {
         count += is_x_mas2(grid, pos);
     }
-    count
+    Ok(count)
 }
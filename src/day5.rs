@@ -2,26 +2,30 @@ use std::{cmp::Ordering, collections::HashSet};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::parse::{self, ParseError};
+
+pub type Manual = (HashSet<(u8, u8)>, Vec<Vec<u8>>);
+
 #[aoc_generator(day5)]
-pub fn input_generator(input: &str) -> (HashSet<(u8, u8)>, Vec<Vec<u8>>) {
+pub fn input_generator(input: &str) -> Result<Manual, ParseError> {
     let mut ordering = HashSet::new();
     let mut numbers = Vec::new();
-    let mut is_first_section = true;
-    for line in input.lines() {
-        if line.is_empty() {
-            is_first_section = false;
-        } else if is_first_section {
-            let (left, right) = line.split_once('|').unwrap();
-            ordering.insert((left.parse().unwrap(), right.parse().unwrap()));
-        } else {
-            numbers.push(line.split(',').map(|x| x.parse::<u8>().unwrap()).collect());
-        }
+    let mut sections = parse::blocks(input);
+    for line in sections.next().unwrap_or_default().lines() {
+        let (left, right) = parse::key_value(line, '|')?;
+        ordering.insert((parse::number(left)?, parse::number(right)?));
     }
-    (ordering, numbers)
+    for line in sections.next().unwrap_or_default().lines() {
+        numbers.push(parse::ints(line)?);
+    }
+    Ok((ordering, numbers))
 }
 
 //returns 0 if not valid
-pub fn valid(number: &Vec<u8>, ordering: &HashSet<(u8, u8)>) -> u8 {
+pub fn valid(number: &[u8], ordering: &HashSet<(u8, u8)>) -> u8 {
+    if number.is_empty() {
+        return 0;
+    }
     for i in 0..number.len() - 1 {
         for j in i + 1..number.len() {
             if ordering.contains(&(number[j], number[i])) {
@@ -32,7 +36,7 @@ pub fn valid(number: &Vec<u8>, ordering: &HashSet<(u8, u8)>) -> u8 {
     number[number.len() / 2]
 }
 #[aoc(day5, part1)]
-pub fn part1((ordering, numbers): &(HashSet<(u8, u8)>, Vec<Vec<u8>>)) -> u32 {
+pub fn part1((ordering, numbers): &Manual) -> u32 {
     let mut sum = 0;
     for number in numbers {
         sum += valid(number, ordering) as u32;
@@ -41,7 +45,7 @@ pub fn part1((ordering, numbers): &(HashSet<(u8, u8)>, Vec<Vec<u8>>)) -> u32 {
 }
 
 #[aoc(day5, part2)]
-pub fn part2((ordering, numbers): &(HashSet<(u8, u8)>, Vec<Vec<u8>>)) -> u32 {
+pub fn part2((ordering, numbers): &Manual) -> u32 {
     let mut sum = 0;
     for number in numbers {
         if valid(number, ordering) == 0 {
@@ -55,7 +59,7 @@ pub fn part2((ordering, numbers): &(HashSet<(u8, u8)>, Vec<Vec<u8>>)) -> u32 {
                     Ordering::Equal
                 }
             });
-            sum += number[number.len() / 2] as u32;
+            sum += number.get(number.len() / 2).copied().unwrap_or(0) as u32;
         }
     }
     sum
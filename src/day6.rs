@@ -1,15 +1,12 @@
-use std::collections::HashSet;
+use std::error::Error;
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_runner_derive::aoc;
+
+use crate::bitset::GridSet;
+use crate::grid::Grid;
+use crate::parallel;
+use crate::parse::ParseError;
 
-#[aoc_generator(day6)]
-pub fn input_generator(input: &str) -> Vec<Vec<char>> {
-    let mut grid = Vec::new();
-    for line in input.lines() {
-        grid.push(line.chars().collect::<Vec<char>>());
-    }
-    grid
-}
 #[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
 pub enum Direction {
     Up,    // (0, 1)
@@ -28,6 +25,17 @@ impl Direction {
         }
     }
 
+    // the arrows the puzzles draw a direction with
+    pub fn from_glyph(glyph: u8) -> Option<Self> {
+        match glyph {
+            b'^' => Some(Direction::Up),
+            b'>' => Some(Direction::Right),
+            b'v' => Some(Direction::Down),
+            b'<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
     pub fn next(&self) -> Self {
         match self {
             Direction::Up => Direction::Right,
@@ -38,68 +46,72 @@ impl Direction {
     }
 }
 
-pub fn find_start_pos(grid: &Vec<Vec<char>>) -> (usize, usize) {
-    for i in 0..grid.len() {
-        for j in 0..grid[0].len() {
-            if grid[i][j] == '^' {
-                return (i, j);
-            }
-        }
-    }
-    panic!()
+pub fn find_start_pos(grid: &Grid) -> Result<(usize, usize), ParseError> {
+    grid.find(b'^').ok_or(ParseError::Missing("guard '^'"))
 }
 
 #[aoc(day6, part1)]
-pub fn part1(grid: &Vec<Vec<char>>) -> u32 {
-    let mut grid = grid.clone();
-    let mut pos = find_start_pos(&grid);
+pub fn part1(input: &str) -> Result<u32, Box<dyn Error>> {
+    let mut grid = Grid::parse(input)?;
+    let mut pos = find_start_pos(&grid)?;
 
-    const BARRIER: char = '#';
-    const UNVISITED: char = '.';
-    const VISITED: char = 'X';
+    const BARRIER: u8 = b'#';
+    const UNVISITED: u8 = b'.';
+    const VISITED: u8 = b'X';
 
     let mut dir = Direction::Up;
     let mut in_bound = true;
     let mut visited_count = 0;
+    // every cell can be entered at most once per direction before the walk
+    // repeats itself
+    let max_steps = grid.rows() * grid.cols() * 4;
 
-    grid[pos.0][pos.1] = VISITED;
+    grid.set(pos, VISITED);
     visited_count += 1;
-    while in_bound {
+    for _ in 0..=max_steps {
         let next_pos = (
             (pos.0 as i32 + dir.offset().0) as usize,
             (pos.1 as i32 + dir.offset().1) as usize,
         );
-        in_bound = next_pos.0 < grid.len() && next_pos.1 < grid[0].len();
+        in_bound = grid.in_bounds(next_pos);
         if !in_bound {
             break;
         }
-        match grid[next_pos.0][next_pos.1] {
+        match grid[next_pos] {
             BARRIER => dir = dir.next(),
             UNVISITED => {
-                grid[next_pos.0][next_pos.1] = VISITED;
+                grid.set(next_pos, VISITED);
                 pos = next_pos;
                 visited_count += 1;
             }
             VISITED => pos = next_pos,
-            _ => panic!(),
+            cell => return Err(format!("unexpected {:?} on the map", cell as char).into()),
         }
     }
-    visited_count
+    if in_bound {
+        return Err("the guard never leaves the map".into());
+    }
+    Ok(visited_count)
 }
 
-pub fn has_loop(grid: Vec<Vec<char>>, start_pos: (usize, usize)) -> bool {
-    const BARRIER: char = '#';
+// `obstacle` is treated as an extra barrier, so candidates can be tried
+// without touching the grid
+pub fn has_loop(
+    grid: &Grid,
+    start_pos: (usize, usize),
+    obstacle: (usize, usize),
+    visited: &mut GridSet,
+) -> bool {
+    const BARRIER: u8 = b'#';
 
     let mut dir = Direction::Up;
     let mut in_bound = true;
     let mut has_loop = false;
-    let mut visited: HashSet<((usize, usize), Direction)> = HashSet::new();
     let mut pos = start_pos;
+    visited.clear();
 
     while in_bound {
-        let current_state = (pos, dir);
-
-        if visited.contains(&current_state) {
+        if visited.contains_layer(pos, dir as usize) {
             has_loop = true;
             break;
         }
@@ -107,13 +119,13 @@ pub fn has_loop(grid: Vec<Vec<char>>, start_pos: (usize, usize)) -> bool {
             (pos.0 as i32 + dir.offset().0) as usize,
             (pos.1 as i32 + dir.offset().1) as usize,
         );
-        in_bound = next_pos.0 < grid.len() && next_pos.1 < grid[0].len();
+        in_bound = grid.in_bounds(next_pos);
         if !in_bound {
             break;
         }
 
-        visited.insert(current_state);
-        if grid[next_pos.0][next_pos.1] == BARRIER {
+        visited.insert_layer(pos, dir as usize);
+        if grid[next_pos] == BARRIER || next_pos == obstacle {
             dir = dir.next();
         } else {
             pos = next_pos;
@@ -123,29 +135,19 @@ pub fn has_loop(grid: Vec<Vec<char>>, start_pos: (usize, usize)) -> bool {
 }
 
 #[aoc(day6, part2)]
-pub fn part2(grid: &Vec<Vec<char>>) -> u32 {
-    let mut loop_count = 0;
-    let start_pos = find_start_pos(grid);
-    let mut grid = grid.clone();
-    grid[start_pos.0][start_pos.1] = '.';
-
-    for row in 0..grid.len() {
-        for col in 0..grid[0].len() {
-            if (row, col) == start_pos {
-                continue;
-            }
-
-            if grid[row][col] == '#' {
-                continue;
-            } else {
-                let mut grid_clone = grid.clone();
-                grid_clone[row][col] = '#';
-                if has_loop(grid_clone, start_pos) {
-                    loop_count += 1;
-                }
-            }
-        }
-    }
-
-    loop_count
+pub fn part2(input: &str) -> Result<u32, ParseError> {
+    let grid = Grid::parse(input)?;
+    let start_pos = find_start_pos(&grid)?;
+    let candidates: Vec<(usize, usize)> = grid
+        .iter()
+        .filter(|&(pos, cell)| pos != start_pos && cell != b'#')
+        .map(|(pos, _)| pos)
+        .collect();
+
+    let loops = parallel::map_init(
+        &candidates,
+        || GridSet::with_layers(grid.rows(), grid.cols(), 4),
+        |visited, &obstacle| has_loop(&grid, start_pos, obstacle, visited),
+    );
+    Ok(loops.into_iter().filter(|&found| found).count() as u32)
 }
@@ -1,22 +1,25 @@
-use std::iter::repeat;
-
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 
+use crate::parallel;
+use crate::parse::{self, ParseError};
+
 #[aoc_generator(day7)]
-pub fn input_generator(input: &str) -> Vec<(u128, Vec<u128>)> {
+pub fn input_generator(input: &str) -> Result<Vec<(u128, Vec<u128>)>, ParseError> {
     let mut equations: Vec<(u128, Vec<u128>)> = Vec::new();
     for line in input.lines() {
-        let (left, right) = line.split_once(':').unwrap();
-        let left = left.parse::<u128>().unwrap();
-        let right = right
-            .trim()
-            .split_ascii_whitespace()
-            .map(|x| x.parse::<u128>().unwrap())
-            .collect();
-        equations.push((left, right));
+        let (left, right) = parse::key_value(line, ':')?;
+        let nums = parse::ints(right)?;
+        if nums.is_empty() {
+            return Err(ParseError::WrongCount {
+                line: line.to_string(),
+                expected: 1,
+                found: 0,
+            });
+        }
+        equations.push((parse::number(left)?, nums));
     }
-    equations
+    Ok(equations)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
@@ -28,8 +31,7 @@ pub enum Op {
 
 pub fn generate_operators_combination_part1(size: usize) -> Vec<Vec<Op>> {
     let choices = [Op::Plus, Op::Multiply];
-    repeat(choices.iter())
-        .take(size)
+    std::iter::repeat_n(choices.iter(), size)
         .multi_cartesian_product()
         .map(|x| x.into_iter().cloned().collect())
         .collect()
@@ -37,53 +39,115 @@ pub fn generate_operators_combination_part1(size: usize) -> Vec<Vec<Op>> {
 
 pub fn generate_operators_combination_part2(size: usize) -> Vec<Vec<Op>> {
     let choices = [Op::Plus, Op::Multiply, Op::Concatenate];
-    repeat(choices.iter())
-        .take(size)
+    std::iter::repeat_n(choices.iter(), size)
         .multi_cartesian_product()
         .map(|x| x.into_iter().cloned().collect())
         .collect()
 }
 
-pub fn concat(first: u128, second: u128) -> u128 {
+// None once the result no longer fits
+pub fn concat(first: u128, second: u128) -> Option<u128> {
     let first_str = first.to_string();
     let second_str = second.to_string();
     let concat_str = first_str + &second_str;
-    concat_str.parse::<u128>().unwrap()
+    concat_str.parse::<u128>().ok()
 }
 
-pub fn eval(num: &[u128], operators: &[Op]) -> u128 {
+pub fn eval(num: &[u128], operators: &[Op]) -> Option<u128> {
     let mut result = num[0];
     for i in 1..num.len() {
         result = match operators[i - 1] {
-            Op::Plus => result + num[i],
-            Op::Multiply => result * num[i],
-            Op::Concatenate => concat(result, num[i]),
+            Op::Plus => result.checked_add(num[i])?,
+            Op::Multiply => result.checked_mul(num[i])?,
+            Op::Concatenate => concat(result, num[i])?,
         }
     }
-    result
+    Some(result)
 }
 
+// every operator combination is listed up front, so equations longer than
+// any in the real input are refused instead of exhausting memory
+const NAIVE_MAX_NUMBERS: usize = 13;
+
 pub fn calibrate_result(
     (lhs, rhs): &(u128, Vec<u128>),
     generate_comb_fn: fn(usize) -> Vec<Vec<Op>>,
-) -> u128 {
+) -> Result<u128, String> {
+    if rhs.len() > NAIVE_MAX_NUMBERS {
+        return Err(format!(
+            "the naive search takes at most {} numbers, found {}",
+            NAIVE_MAX_NUMBERS,
+            rhs.len()
+        ));
+    }
     for operations in generate_comb_fn(rhs.len() - 1) {
-        if *lhs == eval(rhs, &operations) {
-            return *lhs;
+        if eval(rhs, &operations) == Some(*lhs) {
+            return Ok(*lhs);
         }
     }
-    0
+    Ok(0)
+}
+// works backwards from the test value: the last number was either added,
+// multiplied or concatenated onto whatever the numbers before it make
+pub fn can_make(target: u128, nums: &[u128], with_concat: bool) -> bool {
+    let (&last, rest) = nums.split_last().unwrap();
+    if rest.is_empty() {
+        return target == last;
+    }
+    if target >= last && can_make(target - last, rest, with_concat) {
+        return true;
+    }
+    let multiplied = if last == 0 {
+        target == 0
+    } else {
+        target.is_multiple_of(last) && can_make(target / last, rest, with_concat)
+    };
+    if multiplied {
+        return true;
+    }
+    if with_concat && target >= last {
+        let prefix = target - last;
+        return match 10u128.checked_pow(last.checked_ilog10().unwrap_or(0) + 1) {
+            Some(shift) => {
+                prefix.is_multiple_of(shift) && can_make(prefix / shift, rest, with_concat)
+            }
+            // only a leading 0 can be concatenated onto a number this long
+            None => prefix == 0 && can_make(0, rest, with_concat),
+        };
+    }
+    false
 }
+
 #[aoc(day7, part1)]
-pub fn part1(equations: &Vec<(u128, Vec<u128>)>) -> u128 {
+pub fn part1(equations: &[(u128, Vec<u128>)]) -> u128 {
+    parallel::map(equations, |(lhs, rhs)| match can_make(*lhs, rhs, false) {
+        true => *lhs,
+        false => 0,
+    })
+    .into_iter()
+    .sum()
+}
+
+#[aoc(day7, part2)]
+pub fn part2(equations: &[(u128, Vec<u128>)]) -> u128 {
+    parallel::map(equations, |(lhs, rhs)| match can_make(*lhs, rhs, true) {
+        true => *lhs,
+        false => 0,
+    })
+    .into_iter()
+    .sum()
+}
+
+#[aoc(day7, part1, Naive)]
+pub fn part1_naive(equations: &[(u128, Vec<u128>)]) -> Result<u128, String> {
     equations
         .iter()
         .map(|e| calibrate_result(e, generate_operators_combination_part1))
         .sum()
 }
 
-#[aoc(day7, part2)]
-pub fn part2(equations: &Vec<(u128, Vec<u128>)>) -> u128 {
+#[aoc(day7, part2, Naive)]
+pub fn part2_naive(equations: &[(u128, Vec<u128>)]) -> Result<u128, String> {
     equations
         .iter()
         .map(|e| calibrate_result(e, generate_operators_combination_part2))
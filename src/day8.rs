@@ -1,35 +1,27 @@
 use std::collections::{HashMap, HashSet};
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_runner_derive::aoc;
 use itertools::Itertools;
 
-#[aoc_generator(day8)]
-pub fn input_generator(input: &str) -> Vec<Vec<char>> {
-    let mut grid = Vec::new();
-    for line in input.lines() {
-        grid.push(line.chars().collect());
-    }
-    grid
-}
+use crate::grid::Grid;
+use crate::parse::ParseError;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Coord(usize, usize);
 
-pub fn scan_antennas(grid: &Vec<Vec<char>>) -> HashMap<char, Vec<Coord>> {
+pub fn scan_antennas(grid: &Grid) -> HashMap<u8, Vec<Coord>> {
     let mut antennas_coordinates = HashMap::new();
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &element) in row.iter().enumerate() {
-            if element == '.' {
-                continue;
-            } else {
-                let coord = Coord(i, j);
-                antennas_coordinates
-                    .entry(element)
-                    .and_modify(|coor: &mut Vec<Coord>| {
-                        coor.push(coord);
-                    })
-                    .or_insert(vec![coord]);
-            }
+    for ((i, j), element) in grid.iter() {
+        if element == b'.' {
+            continue;
+        } else {
+            let coord = Coord(i, j);
+            antennas_coordinates
+                .entry(element)
+                .and_modify(|coor: &mut Vec<Coord>| {
+                    coor.push(coord);
+                })
+                .or_insert(vec![coord]);
         }
     }
     antennas_coordinates
@@ -45,7 +37,9 @@ pub fn antinodes_from_pair(
     max_row: usize,
     max_col: usize,
 ) -> HashSet<Coord> {
-    let f = |a, b| 2 * a - b;
+    // wraps around to a huge coordinate when it falls off the top or left,
+    // which in_bounds then rejects
+    let f = |a: usize, b: usize| (2 * a).wrapping_sub(b);
     let antinodes_1 = Coord(
         f(first_coord.0, second_coord.0),
         f(first_coord.1, second_coord.1),
@@ -66,8 +60,8 @@ pub fn antinodes_from_pair(
 }
 
 pub fn find_antinodes(
-    grid: &Vec<Vec<char>>,
-    antennas: &Vec<Coord>,
+    grid: &Grid,
+    antennas: &[Coord],
     from_pair_fn: fn(
         first_coord: &Coord,
         second_coord: &Coord,
@@ -78,23 +72,24 @@ pub fn find_antinodes(
     let pairs_iter = antennas.iter().tuple_combinations::<(_, _)>();
     let mut antinodes_coord_for_freq = HashSet::new();
     for pair in pairs_iter {
-        let found_antinodes = from_pair_fn(pair.0, pair.1, grid.len(), grid[0].len());
+        let found_antinodes = from_pair_fn(pair.0, pair.1, grid.rows(), grid.cols());
         antinodes_coord_for_freq.extend(found_antinodes.iter());
     }
     antinodes_coord_for_freq
 }
 
 #[aoc(day8, part1)]
-pub fn part1(grid: &Vec<Vec<char>>) -> usize {
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    let grid = Grid::parse(input)?;
     let mut antinodes_coordinates = HashSet::new();
-    let antennas_coordinates = scan_antennas(grid);
-    for (_freq, antennas) in &antennas_coordinates {
-        let antinodes_coord_for_freq = find_antinodes(grid, antennas, antinodes_from_pair);
+    let antennas_coordinates = scan_antennas(&grid);
+    for antennas in antennas_coordinates.values() {
+        let antinodes_coord_for_freq = find_antinodes(&grid, antennas, antinodes_from_pair);
         for coord in antinodes_coord_for_freq {
             antinodes_coordinates.insert(coord);
         }
     }
-    antinodes_coordinates.len()
+    Ok(antinodes_coordinates.len())
 }
 
 pub fn gcd(a: i32, b: i32) -> i32 {
@@ -120,7 +115,7 @@ pub fn antinodes_from_pair_part2(
     let dr = row_diff / g;
     let dc = col_diff / g;
     //first side
-    let mut pos = first_coord.clone();
+    let mut pos = *first_coord;
     loop {
         valid_antinodes.insert(pos);
         let next_row = (pos.0 as i32 + dr) as usize;
@@ -146,36 +141,16 @@ pub fn antinodes_from_pair_part2(
     valid_antinodes
 }
 
-pub fn dbg_prt(grid: &Vec<Vec<char>>) {
-    for row in grid {
-        for ch in row {
-            print!("{}", ch);
-        }
-        println!();
-    }
-}
 #[aoc(day8, part2)]
-pub fn part2(grid: &Vec<Vec<char>>) -> usize {
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    let grid = Grid::parse(input)?;
     let mut antinodes_coordinates = HashSet::new();
-    let antennas_coordinates = scan_antennas(grid);
-    for (_freq, antennas) in &antennas_coordinates {
-        let antinodes_coord_for_freq = find_antinodes(grid, antennas, antinodes_from_pair_part2);
+    let antennas_coordinates = scan_antennas(&grid);
+    for antennas in antennas_coordinates.values() {
+        let antinodes_coord_for_freq = find_antinodes(&grid, antennas, antinodes_from_pair_part2);
         for coord in antinodes_coord_for_freq {
             antinodes_coordinates.insert(coord);
         }
     }
-
-    dbg_prt(grid);
-    println!("----------------------------------------------");
-    let mut grid = grid.clone();
-    for i in 0..grid.len() {
-        for j in 0..grid[0].len() {
-            if antinodes_coordinates.contains(&Coord(i, j)) && grid[i][j] == '.' {
-                grid[i][j] = '#'
-            }
-        }
-    }
-    dbg_prt(&grid);
-
-    antinodes_coordinates.len()
+    Ok(antinodes_coordinates.len())
 }
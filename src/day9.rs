@@ -1,16 +1,19 @@
-use std::{fmt, iter::repeat};
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::parse::ParseError;
+
 #[aoc_generator(day9)]
-pub fn input_generator(input: &str) -> Vec<u8> {
+pub fn input_generator(input: &str) -> Result<Vec<u8>, ParseError> {
     let mut v = vec![];
-    for ch in input.chars() {
-        if ch.is_numeric() {
-            v.push(ch.to_digit(10).unwrap() as u8);
+    for ch in input.trim_end().chars() {
+        match ch.to_digit(10) {
+            Some(digit) => v.push(digit as u8),
+            None => return Err(ParseError::InvalidNumber(ch.to_string())),
         }
     }
-    v
+    Ok(v)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -21,10 +24,7 @@ pub enum Block {
 
 impl Block {
     fn is_occupied(&self) -> bool {
-        match self {
-            Self::Occupied(_) => true,
-            _ => false,
-        }
+        matches!(self, Self::Occupied(_))
     }
 }
 
@@ -36,18 +36,16 @@ impl fmt::Display for Block {
         }
     }
 }
-pub fn to_blocks(disk_map: &Vec<u8>) -> Vec<Block> {
+pub fn to_blocks(disk_map: &[u8]) -> Vec<Block> {
     let mut id = 0u64;
     let mut blocks = vec![];
     let chunks_iter = disk_map.chunks_exact(2);
     for pair in chunks_iter {
         if let [occupied_size, free_size] = pair {
-            let occupied_block: Vec<Block> = repeat(id)
-                .take(*occupied_size as usize)
-                .map(|x| Block::Occupied(x))
+            let occupied_block: Vec<Block> = std::iter::repeat_n(id, *occupied_size as usize)
+                .map(Block::Occupied)
                 .collect();
-            let free_block: Vec<Block> = repeat('.')
-                .take(*free_size as usize)
+            let free_block: Vec<Block> = std::iter::repeat_n('.', *free_size as usize)
                 .map(|_| Block::Empty)
                 .collect();
             blocks.extend(occupied_block);
@@ -60,18 +58,19 @@ pub fn to_blocks(disk_map: &Vec<u8>) -> Vec<Block> {
     let remainder = disk_map.chunks_exact(2).remainder();
     if !remainder.is_empty() {
         let occupied_size = remainder[0];
-        let occupied_block: Vec<Block> = repeat(id)
-            .take(occupied_size as usize)
-            .map(|x| Block::Occupied(x))
+        let occupied_block: Vec<Block> = std::iter::repeat_n(id, occupied_size as usize)
+            .map(Block::Occupied)
             .collect();
         blocks.extend(occupied_block);
     }
     blocks
 }
 
-pub fn move_block(blocks: &mut Vec<Block>) {
+pub fn move_block(blocks: &mut [Block]) {
+    let Some(mut left) = blocks.iter().position(|&b| !b.is_occupied()) else {
+        return;
+    };
     let mut right = blocks.len() - 1;
-    let mut left = blocks.iter().position(|&b| !b.is_occupied()).unwrap();
     while left < right {
         match (blocks[left], blocks[right]) {
             (Block::Empty, Block::Occupied(_)) => {
@@ -90,7 +89,7 @@ pub fn move_block(blocks: &mut Vec<Block>) {
     }
 }
 
-pub fn checksum(blocks: &Vec<Block>) -> u128 {
+pub fn checksum(blocks: &[Block]) -> u128 {
     let mut checksum = 0;
     for (i, b) in blocks.iter().enumerate() {
         match b {
@@ -101,21 +100,18 @@ pub fn checksum(blocks: &Vec<Block>) -> u128 {
     checksum
 }
 
-pub fn print_blocks(blocks: &Vec<Block>) {
+pub fn print_blocks(blocks: &[Block]) {
     blocks.iter().for_each(|x| print!("{}", x));
     println!()
 }
 #[aoc(day9, part1)]
-pub fn part1(disk_map: &Vec<u8>) -> u128 {
+pub fn part1(disk_map: &[u8]) -> u128 {
     let mut blocks = to_blocks(disk_map);
-    print_blocks(&blocks);
-    dbg!(find_occupied_segment(&blocks, max_id(&blocks)));
     move_block(&mut blocks);
-    print_blocks(&blocks);
     checksum(&blocks)
 }
 
-pub fn max_id(blocks: &Vec<Block>) -> u64 {
+pub fn max_id(blocks: &[Block]) -> u64 {
     blocks
         .iter()
         .filter_map(|b| match b {
@@ -126,7 +122,7 @@ pub fn max_id(blocks: &Vec<Block>) -> u64 {
         .unwrap_or(0)
 }
 
-pub fn find_occupied_segment(blocks: &Vec<Block>, id: u64) -> Option<(usize, usize)> {
+pub fn find_occupied_segment(blocks: &[Block], id: u64) -> Option<(usize, usize)> {
     let mut start: Option<usize> = None;
     let mut length = 0usize;
     for (i, block) in blocks.iter().enumerate() {
@@ -148,77 +144,121 @@ pub fn find_occupied_segment(blocks: &Vec<Block>, id: u64) -> Option<(usize, usi
 }
 
 #[aoc(day9, part2)]
-pub fn part2(disk_map: &Vec<u8>) -> u128 {
+pub fn part2(disk_map: &[u8]) -> u128 {
+    // start of every free span, bucketed by the span's length
+    let mut free_spans: [BinaryHeap<Reverse<usize>>; 10] = Default::default();
+    let mut files = vec![];
+    let mut pos = 0;
+    for (i, &size) in disk_map.iter().enumerate() {
+        let size = size as usize;
+        if i % 2 == 0 {
+            files.push((pos, size));
+        } else if size > 0 {
+            free_spans[size].push(Reverse(pos));
+        }
+        pos += size;
+    }
+
+    let mut checksum = 0;
+    for (id, &(file_start, file_len)) in files.iter().enumerate().rev() {
+        if file_len == 0 {
+            continue;
+        }
+        // leftmost span that is big enough, space freed by moved files is
+        // always right of the files still to move so it is never reused
+        let target = (file_len..10)
+            .filter_map(|len| free_spans[len].peek().map(|&Reverse(start)| (start, len)))
+            .filter(|&(start, _)| start < file_start)
+            .min();
+        let new_start = match target {
+            Some((span_start, span_len)) => {
+                free_spans[span_len].pop();
+                if span_len > file_len {
+                    free_spans[span_len - file_len].push(Reverse(span_start + file_len));
+                }
+                span_start
+            }
+            None => file_start,
+        };
+        for block in new_start..new_start + file_len {
+            checksum += block as u128 * id as u128;
+        }
+    }
+    checksum
+}
+
+#[aoc(day9, part2, Naive)]
+pub fn part2_naive(disk_map: &[u8]) -> u128 {
     let mut blocks = to_blocks(disk_map);
     let max_id = max_id(&blocks);
     for current_id in (0..=max_id).rev() {
         let segment = find_occupied_segment(&blocks, current_id);
 
-        if let Some((file_start, file_len)) = segment {
-            if file_len > 0 {
-                // --- Find the target empty slot ---
-                let mut target_start_opt: Option<usize> = None;
-                let mut current_empty_start: Option<usize> = None;
-                let mut current_empty_len = 0;
-
-                // Search *only to the left* of the file (indices 0 to file_start - 1)
-                for i in 0..file_start {
-                    if blocks[i] == Block::Empty {
-                        if current_empty_start.is_none() {
-                            current_empty_start = Some(i); // Mark start of potential slot
-                        }
-                        current_empty_len += 1;
-                    } else {
-                        // Just finished a sequence of empty blocks (or hit an occupied one)
-                        if let Some(start) = current_empty_start {
-                            if current_empty_len >= file_len {
-                                // Found a suitable slot! It's the leftmost one so far.
-                                target_start_opt = Some(start);
-                                break; // Stop searching immediately
-                            }
-                        }
-                        // Reset for the next potential empty sequence
-                        current_empty_start = None;
-                        current_empty_len = 0;
-                    }
-                }
+        if let Some((file_start, file_len)) = segment
+            && file_len > 0
+        {
+            // --- Find the target empty slot ---
+            let mut target_start_opt: Option<usize> = None;
+            let mut current_empty_start: Option<usize> = None;
+            let mut current_empty_len = 0;
 
-                // Check if the search ended while in a potential empty block sequence
-                if target_start_opt.is_none() {
-                    // Only if we haven't already found a slot
-                    if let Some(start) = current_empty_start {
-                        if current_empty_len >= file_len {
-                            target_start_opt = Some(start);
-                        }
+            // Search *only to the left* of the file (indices 0 to file_start - 1)
+            for (i, block) in blocks.iter().enumerate().take(file_start) {
+                if *block == Block::Empty {
+                    if current_empty_start.is_none() {
+                        current_empty_start = Some(i); // Mark start of potential slot
+                    }
+                    current_empty_len += 1;
+                } else {
+                    // Just finished a sequence of empty blocks (or hit an occupied one)
+                    if let Some(start) = current_empty_start
+                        && current_empty_len >= file_len
+                    {
+                        // Found a suitable slot! It's the leftmost one so far.
+                        target_start_opt = Some(start);
+                        break; // Stop searching immediately
                     }
+                    // Reset for the next potential empty sequence
+                    current_empty_start = None;
+                    current_empty_len = 0;
                 }
+            }
 
-                // --- Perform the move if a target was found ---
-                if let Some(target_start) = target_start_opt {
-                    // Move the file:
-                    // 1. Copy the file blocks (Block::Occupied(current_id)) to a temp buffer or note the ID/length.
-                    // 2. Overwrite the original file location (file_start to file_start + file_len - 1) with Block::Empty.
-                    // 3. Overwrite the target location (target_start to target_start + file_len - 1) with Block::Occupied(current_id).
+            // Check if the search ended while in a potential empty block sequence
+            if target_start_opt.is_none() {
+                // Only if we haven't already found a slot
+                if let Some(start) = current_empty_start
+                    && current_empty_len >= file_len
+                {
+                    target_start_opt = Some(start);
+                }
+            }
 
-                    // Example implementation (using simple loops):
-                    let file_block_type = Block::Occupied(current_id);
-                    let empty_block_type = Block::Empty;
+            // --- Perform the move if a target was found ---
+            if let Some(target_start) = target_start_opt {
+                // Move the file:
+                // 1. Copy the file blocks (Block::Occupied(current_id)) to a temp buffer or note the ID/length.
+                // 2. Overwrite the original file location (file_start to file_start + file_len - 1) with Block::Empty.
+                // 3. Overwrite the target location (target_start to target_start + file_len - 1) with Block::Occupied(current_id).
 
-                    // Clear original spot
-                    for i in 0..file_len {
-                        blocks[file_start + i] = empty_block_type;
-                    }
-                    // Fill target spot
-                    for i in 0..file_len {
-                        blocks[target_start + i] = file_block_type;
-                    }
+                // Example implementation (using simple loops):
+                let file_block_type = Block::Occupied(current_id);
+                let empty_block_type = Block::Empty;
 
-                    // Optional: Print state after move for debugging
-                    // println!("Moved file {}:", current_id);
-                    // print_blocks(&blocks);
+                // Clear original spot
+                for i in 0..file_len {
+                    blocks[file_start + i] = empty_block_type;
+                }
+                // Fill target spot
+                for i in 0..file_len {
+                    blocks[target_start + i] = file_block_type;
                 }
-                // else: No suitable slot found, file `current_id` does not move.
+
+                // Optional: Print state after move for debugging
+                // println!("Moved file {}:", current_id);
+                // print_blocks(&blocks);
             }
+            // else: No suitable slot found, file `current_id` does not move.
         }
     }
     checksum(&blocks)
@@ -0,0 +1,91 @@
+// C interface to the solutions, built into the cdylib. The header in
+// include/aoc.h is generated from this file with the cbindgen CLI and
+// committed; rerun it after changing anything here:
+//   cbindgen --config cbindgen.toml --output include/aoc.h src/ffi.rs
+
+use std::{ffi::c_char, slice};
+
+use crate::{registry, runner};
+
+/// The answer was written to `out`.
+pub const AOC_OK: i32 = 0;
+/// `input` or `out_len` was null, or `out` was null with a nonzero `out_cap`.
+pub const AOC_ERR_NULL_POINTER: i32 = 1;
+/// There is no solution for that year, day and part.
+pub const AOC_ERR_UNKNOWN_PUZZLE: i32 = 2;
+/// The input is not UTF-8.
+pub const AOC_ERR_INVALID_INPUT: i32 = 3;
+/// The solution failed or panicked; `out` holds the error message.
+pub const AOC_ERR_SOLVE_FAILED: i32 = 4;
+/// `out` is too small; `*out_len` is set to the size needed.
+pub const AOC_ERR_BUFFER_TOO_SMALL: i32 = 5;
+
+/// Solves one part of a puzzle with its main implementation.
+///
+/// `input` points to `input_len` bytes of puzzle input. On `AOC_OK` and
+/// `AOC_ERR_SOLVE_FAILED` the answer or error message is written to `out`
+/// as a NUL-terminated string and `*out_len` is set to its length without
+/// the NUL. When it does not fit in `out_cap` bytes nothing is written,
+/// `*out_len` is set to the capacity needed including the NUL and
+/// `AOC_ERR_BUFFER_TOO_SMALL` is returned, so a caller can retry.
+///
+/// # Safety
+///
+/// `input` must be valid for reads of `input_len` bytes, `out` for writes
+/// of `out_cap` bytes and `out_len` for a write of one `size_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out: *mut c_char,
+    out_cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    if input.is_null() || out_len.is_null() || (out.is_null() && out_cap > 0) {
+        return AOC_ERR_NULL_POINTER;
+    }
+    let Some(solution) = (year == 2024)
+        .then(|| registry::implementations(day, part))
+        .and_then(|found| found.first().copied())
+    else {
+        return AOC_ERR_UNKNOWN_PUZZLE;
+    };
+    let input = unsafe { slice::from_raw_parts(input, input_len) };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AOC_ERR_INVALID_INPUT;
+    };
+
+    let (code, text) = match runner::solve_catching(solution, input) {
+        Ok(answer) => (AOC_OK, answer),
+        Err(e) => (AOC_ERR_SOLVE_FAILED, e),
+    };
+    if text.len() + 1 > out_cap {
+        unsafe { *out_len = text.len() + 1 };
+        return AOC_ERR_BUFFER_TOO_SMALL;
+    }
+    unsafe {
+        let out = slice::from_raw_parts_mut(out.cast::<u8>(), out_cap);
+        out[..text.len()].copy_from_slice(text.as_bytes());
+        out[text.len()] = 0;
+        *out_len = text.len();
+    }
+    code
+}
+
+/// A static, NUL-terminated description of an `AOC_*` code.
+#[unsafe(no_mangle)]
+pub extern "C" fn aoc_strerror(code: i32) -> *const c_char {
+    let message: &'static [u8] = match code {
+        AOC_OK => b"ok\0",
+        AOC_ERR_NULL_POINTER => b"null pointer argument\0",
+        AOC_ERR_UNKNOWN_PUZZLE => b"no solution for that puzzle\0",
+        AOC_ERR_INVALID_INPUT => b"input is not UTF-8\0",
+        AOC_ERR_SOLVE_FAILED => b"the solution failed\0",
+        AOC_ERR_BUFFER_TOO_SMALL => b"output buffer too small\0",
+        _ => b"unknown error code\0",
+    };
+    message.as_ptr().cast()
+}
@@ -0,0 +1,223 @@
+use std::{fs, panic};
+
+use crate::{
+    registry::{self, Solution},
+    rng::Rng,
+    runner, watch,
+};
+
+// robustness harness: every generator and solver is fed mutated puzzle text
+// and has to come back with an answer or an error, a panic is a crash
+
+// frames get written to disk for every call, far too slow to fuzz
+const SKIPPED: &[(u32, u32)] = &[(14, 2)];
+
+// the inputs and examples are cut down to this many lines per blank-line separated
+// block, each at most this wide, so the slow implementations stay quick
+const SEED_LINES: usize = 8;
+const SEED_WIDTH: usize = 40;
+
+const FIXED_SEEDS: &[&str] = &["", "\n", "\n\n", "0", "-1", "^", "#", "X", "1 2 3"];
+
+// bytes that mean something to at least one of the parsers
+const INTERESTING_CHARS: &[char] = &[
+    '0', '1', '9', '-', '+', ' ', ',', ':', '|', '=', '\n', '\r', '#', '.', '^', 'X', 'A', 'é',
+];
+
+const INTERESTING_NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "255",
+    "256",
+    "65536",
+    "4294967296",
+    "18446744073709551616",
+    "99999999999999999999999999999999999999999",
+];
+
+#[derive(Debug, Clone)]
+pub struct Crash {
+    pub solution: &'static Solution,
+    pub input: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cases: 500,
+            seed: 2024,
+        }
+    }
+}
+
+pub fn is_skipped(day: u32, part: u32) -> bool {
+    SKIPPED.contains(&(day, part))
+}
+
+// seed texts for `day`: its own cropped input and examples first, then
+// every other day's so parsers also see text meant for someone else
+pub fn corpus(day: u32) -> Vec<String> {
+    let mut own = vec![];
+    let mut others = vec![];
+    for (d, _) in registry::parts() {
+        let examples = watch::examples(d)
+            .into_iter()
+            .filter_map(|path| fs::read_to_string(path).ok());
+        for input in runner::read_input(d).ok().into_iter().chain(examples) {
+            let cropped = crop(&input);
+            match d == day {
+                true if !own.contains(&cropped) => own.push(cropped),
+                false if !others.contains(&cropped) => others.push(cropped),
+                _ => {}
+            }
+        }
+    }
+    own.extend(others);
+    own.extend(FIXED_SEEDS.iter().map(|s| s.to_string()));
+    own
+}
+
+fn crop(input: &str) -> String {
+    let mut blocks = vec![];
+    for block in input.split("\n\n") {
+        let lines: Vec<String> = block
+            .lines()
+            .take(SEED_LINES)
+            .map(|line| line.chars().take(SEED_WIDTH).collect())
+            .collect();
+        blocks.push(lines.join("\n"));
+    }
+    blocks.join("\n\n") + "\n"
+}
+
+pub fn mutate(rng: &mut Rng, text: &str) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for _ in 0..1 + rng.index(4) {
+        let at = rng.index(chars.len() + 1);
+        let end = (at + 1 + rng.index(16)).min(chars.len());
+        match rng.below(7) {
+            0 => chars.insert(at, *rng.pick(INTERESTING_CHARS)),
+            1 if at < chars.len() => chars[at] = *rng.pick(INTERESTING_CHARS),
+            2 if at < end => {
+                chars.drain(at..end);
+            }
+            3 if at < end => {
+                let copy: Vec<char> = chars[at..end].to_vec();
+                let to = rng.index(chars.len() + 1);
+                chars.splice(to..to, copy);
+            }
+            4 => chars.truncate(at),
+            5 => replace_number(rng, &mut chars, at),
+            _ => swap_lines(rng, &mut chars),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+// swaps the first number at or after `at` for one near a type boundary
+fn replace_number(rng: &mut Rng, chars: &mut Vec<char>, at: usize) {
+    let Some(start) = (at..chars.len()).find(|&i| chars[i].is_ascii_digit()) else {
+        return;
+    };
+    let end = (start..chars.len())
+        .find(|&i| !chars[i].is_ascii_digit())
+        .unwrap_or(chars.len());
+    chars.splice(start..end, rng.pick(INTERESTING_NUMBERS).chars());
+}
+
+fn swap_lines(rng: &mut Rng, chars: &mut Vec<char>) {
+    let text: String = chars.iter().collect();
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    let (a, b) = (rng.index(lines.len()), rng.index(lines.len()));
+    lines.swap(a, b);
+    *chars = lines.join("\n").chars().collect();
+}
+
+fn run_case(solutions: &[&'static Solution], input: &str) -> Option<Crash> {
+    for &solution in solutions {
+        if let Err(message) = runner::solve_catching(solution, input)
+            && message.starts_with("panicked")
+        {
+            return Some(Crash {
+                solution,
+                input: input.to_string(),
+                message,
+            });
+        }
+    }
+    None
+}
+
+// drops lines, then single characters, for as long as the crash stays
+fn minimize(mut crash: Crash) -> Crash {
+    let solution = crash.solution;
+    let still_crashes = |input: &str| run_case(&[solution], input);
+    loop {
+        let lines: Vec<&str> = crash.input.split('\n').collect();
+        let smaller = (0..lines.len()).find_map(|skip| {
+            let input: Vec<&str> = (0..lines.len())
+                .filter(|&i| i != skip)
+                .map(|i| lines[i])
+                .collect();
+            still_crashes(&input.join("\n"))
+        });
+        match smaller {
+            Some(smaller) => crash = smaller,
+            None => break,
+        }
+    }
+    loop {
+        let chars: Vec<char> = crash.input.chars().collect();
+        let smaller = (0..chars.len()).find_map(|skip| {
+            let input: String = (0..chars.len())
+                .filter(|&i| i != skip)
+                .map(|i| chars[i])
+                .collect();
+            still_crashes(&input)
+        });
+        match smaller {
+            Some(smaller) => crash = smaller,
+            None => break,
+        }
+    }
+    crash
+}
+
+// runs `config.cases` mutated inputs through every implementation of the
+// part, the seed texts themselves go first
+pub fn fuzz(day: u32, part: u32, config: Config) -> Result<(), Crash> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = fuzz_quietly(day, part, config);
+    panic::set_hook(hook);
+    result
+}
+
+fn fuzz_quietly(day: u32, part: u32, config: Config) -> Result<(), Crash> {
+    let solutions = registry::implementations(day, part);
+    let corpus = corpus(day);
+    let mut rng = Rng::new(config.seed ^ ((day as u64) << 8 | part as u64));
+    for case in 0..config.cases {
+        let input = match corpus.get(case) {
+            Some(seed) => seed.clone(),
+            // the day's own input is the best starting point, so it is
+            // picked half of the time
+            None if rng.chance(50) => mutate(&mut rng, &corpus[0]),
+            None => {
+                let seed = rng.pick(&corpus).clone();
+                mutate(&mut rng, &seed)
+            }
+        };
+        if let Some(crash) = run_case(&solutions, &input) {
+            return Err(minimize(crash));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::{borrow::Cow, fmt, ops::Index};
+
+use crate::parse::ParseError;
+
+// rectangular grid of bytes that borrows the puzzle input as-is: rows stay
+// separated by their line endings and are found through `stride`. Writing
+// through `set` turns it into an owned copy first (copy-on-write).
+#[derive(Debug, Clone)]
+pub struct Grid<'a> {
+    data: Cow<'a, [u8]>,
+    rows: usize,
+    cols: usize,
+    stride: usize,
+}
+
+impl<'a> Grid<'a> {
+    // every row has to be as wide as the first one; input that mixes \n and
+    // \r\n line endings is copied with the \r dropped so rows stay evenly
+    // spaced
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
+        let data = input.trim_end().as_bytes();
+        let strip_cr = |line: &'a [u8]| line.strip_suffix(b"\r").unwrap_or(line);
+        let lines: Vec<&[u8]> = match data.is_empty() {
+            true => vec![],
+            false => data.split(|&b| b == b'\n').collect(),
+        };
+        let cols = lines.first().map_or(0, |line| strip_cr(line).len());
+        let stride = lines.first().map_or(0, |line| line.len()) + 1;
+        let mut uniform = true;
+        for (row, line) in lines.iter().enumerate() {
+            let found = strip_cr(line).len();
+            if found != cols {
+                return Err(ParseError::RaggedRow {
+                    row,
+                    expected: cols,
+                    found,
+                });
+            }
+            // the last line already lost its ending to trim_end
+            uniform &= row + 1 == lines.len() || line.len() + 1 == stride;
+        }
+        let rows = lines.len();
+        if !uniform {
+            let lines: Vec<&[u8]> = lines.into_iter().map(strip_cr).collect();
+            return Ok(Grid {
+                data: Cow::Owned(lines.join(&b'\n')),
+                rows,
+                cols,
+                stride: cols + 1,
+            });
+        }
+        Ok(Grid {
+            data: Cow::Borrowed(data),
+            rows,
+            cols,
+            stride,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn in_bounds(&self, (row, col): (usize, usize)) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<u8> {
+        self.in_bounds(pos).then(|| self[pos])
+    }
+
+    // position one step of `offset` away from `pos`, if it is still on the grid
+    pub fn step(&self, pos: (usize, usize), offset: (i32, i32)) -> Option<(usize, usize)> {
+        let row = pos.0.checked_add_signed(offset.0 as isize)?;
+        let col = pos.1.checked_add_signed(offset.1 as isize)?;
+        self.in_bounds((row, col)).then_some((row, col))
+    }
+
+    pub fn row(&self, row: usize) -> &[u8] {
+        let start = row * self.stride;
+        &self.data[start..start + self.cols]
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), u8)> + '_ {
+        self.positions().map(|pos| (pos, self[pos]))
+    }
+
+    pub fn find(&self, value: u8) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|&(_, cell)| cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn set(&mut self, (row, col): (usize, usize), value: u8) {
+        assert!(
+            self.in_bounds((row, col)),
+            "({}, {}) is off the grid",
+            row,
+            col
+        );
+        self.data.to_mut()[row * self.stride + col] = value;
+    }
+
+    pub fn is_owned(&self) -> bool {
+        matches!(self.data, Cow::Owned(_))
+    }
+
+    pub fn into_owned(self) -> Grid<'static> {
+        Grid {
+            data: Cow::Owned(self.data.into_owned()),
+            rows: self.rows,
+            cols: self.cols,
+            stride: self.stride,
+        }
+    }
+}
+
+impl Index<(usize, usize)> for Grid<'_> {
+    type Output = u8;
+
+    fn index(&self, (row, col): (usize, usize)) -> &u8 {
+        debug_assert!(col < self.cols, "column {} is off the grid", col);
+        &self.data[row * self.stride + col]
+    }
+}
+
+impl fmt::Display for Grid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..self.rows {
+            writeln!(f, "{}", String::from_utf8_lossy(self.row(row)))?;
+        }
+        Ok(())
+    }
+}
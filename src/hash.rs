@@ -0,0 +1,8 @@
+use std::collections::{HashMap, HashSet};
+
+use rustc_hash::FxBuildHasher;
+
+// std's SipHash is overkill for the small integer keys the solutions hash,
+// these use the Fx hasher instead (build with `::default()`)
+pub type FastHashMap<K, V> = HashMap<K, V, FxBuildHasher>;
+pub type FastHashSet<T> = HashSet<T, FxBuildHasher>;
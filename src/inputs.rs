@@ -0,0 +1,160 @@
+use std::fmt::Write;
+
+use crate::rng::Rng;
+
+// random but puzzle-valid inputs; `size` grows the input roughly linearly
+// (grid side, number of lines, ...) so failures can be retried smaller
+
+pub fn day6(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let rows = 1 + rng.index(size + 1);
+        let cols = 1 + rng.index(size + 1);
+        let mut grid: Vec<Vec<u8>> = (0..rows)
+            .map(|_| {
+                (0..cols)
+                    .map(|_| if rng.chance(15) { b'#' } else { b'.' })
+                    .collect()
+            })
+            .collect();
+        let start = (rng.index(rows), rng.index(cols));
+        grid[start.0][start.1] = b'^';
+        // the puzzle promises the guard walks off the map eventually
+        if guard_walk(&grid, start).is_some() {
+            return to_lines(&grid);
+        }
+    }
+}
+
+// steps the guard takes before leaving the map, None if it never does
+pub fn guard_walk(grid: &[Vec<u8>], start: (usize, usize)) -> Option<usize> {
+    let offsets = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    let (mut pos, mut dir) = (start, 0);
+    let max_steps = grid.len() * grid[0].len() * 4;
+    for steps in 0..=max_steps {
+        let (dr, dc) = offsets[dir];
+        let next = (pos.0.wrapping_add_signed(dr), pos.1.wrapping_add_signed(dc));
+        match grid.get(next.0).and_then(|row| row.get(next.1)) {
+            None => return Some(steps),
+            Some(b'#') => dir = (dir + 1) % 4,
+            Some(_) => pos = next,
+        }
+    }
+    None
+}
+
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let max_value = 9 + size as i64 * 10;
+    for _ in 0..1 + size / 2 {
+        let count = 2 + rng.index(size.min(6) + 1);
+        let nums: Vec<u128> = (0..count)
+            .map(|_| rng.range(1..=max_value) as u128)
+            .collect();
+        let target = if rng.chance(60) {
+            nums[1..]
+                .iter()
+                .fold(nums[0], |acc, &n| match rng.below(3) {
+                    0 => acc + n,
+                    1 => acc * n,
+                    _ => format!("{}{}", acc, n).parse().unwrap(),
+                })
+        } else {
+            rng.range(1..=max_value * max_value) as u128
+        };
+        let nums: Vec<String> = nums.iter().map(u128::to_string).collect();
+        writeln!(input, "{}: {}", target, nums.join(" ")).unwrap();
+    }
+    input
+}
+
+pub fn day9(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for file in 0..=size {
+        input.push(char::from(b'1' + rng.below(9) as u8));
+        if file < size {
+            input.push(char::from(b'0' + rng.below(10) as u8));
+        }
+    }
+    input.push('\n');
+    input
+}
+
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    let rows = 1 + rng.index(size + 1);
+    let cols = 1 + rng.index(size + 1);
+    // heights mostly climb along the diagonal so there are trails to find
+    let grid: Vec<Vec<u8>> = (0..rows)
+        .map(|r| {
+            (0..cols)
+                .map(|c| b'0' + ((r + c) as i64 + rng.range(-1..=1)).rem_euclid(10) as u8)
+                .collect()
+        })
+        .collect();
+    to_lines(&grid)
+}
+
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let rows = 1 + rng.index(size + 1);
+    let cols = 1 + rng.index(size + 1);
+    let plants = [b'A', b'B', b'C', b'D'];
+    let mut grid = vec![vec![b'A'; cols]; rows];
+    for r in 0..rows {
+        for c in 0..cols {
+            // copying a neighbour grows blobs instead of salt-and-pepper noise
+            grid[r][c] = match rng.below(10) {
+                0..3 if r > 0 => grid[r - 1][c],
+                3..6 if c > 0 => grid[r][c - 1],
+                _ => *rng.pick(&plants),
+            };
+        }
+    }
+    to_lines(&grid)
+}
+
+pub fn day13(rng: &mut Rng, size: usize) -> String {
+    let mut machines = vec![];
+    for _ in 0..1 + size / 2 {
+        // real machines never have parallel buttons, but the solver has a
+        // separate path for them, where the cheapest way to the prize may
+        // take more than the 100 presses part 1 allows
+        let parallel = rng.chance(20);
+        let (a, b) = match parallel {
+            true => {
+                let step = (rng.range(1..=9), rng.range(1..=9));
+                let (a, b) = (rng.range(1..=9), rng.range(1..=9));
+                ((step.0 * a, step.1 * a), (step.0 * b, step.1 * b))
+            }
+            false => loop {
+                let a = (rng.range(1..=99), rng.range(1..=99));
+                let b = (rng.range(1..=99), rng.range(1..=99));
+                if a.0 * b.1 != a.1 * b.0 {
+                    break (a, b);
+                }
+            },
+        };
+        let most = if parallel { 200 } else { 100 };
+        let prize = if rng.chance(60) {
+            let (presses_a, presses_b) = (rng.range(0..=most), rng.range(0..=most));
+            (
+                a.0 * presses_a + b.0 * presses_b,
+                a.1 * presses_a + b.1 * presses_b,
+            )
+        } else {
+            (rng.range(0..=20000), rng.range(0..=20000))
+        };
+        machines.push(format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a.0, a.1, b.0, b.1, prize.0, prize.1
+        ));
+    }
+    machines.join("\n")
+}
+
+pub fn to_lines(grid: &[Vec<u8>]) -> String {
+    let mut text = String::new();
+    for row in grid {
+        text.push_str(&String::from_utf8_lossy(row));
+        text.push('\n');
+    }
+    text
+}
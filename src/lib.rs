@@ -14,5 +14,33 @@ pub mod day11;
 pub mod day12;
 pub mod day13;
 pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+
+pub mod alloc;
+pub mod bitset;
+pub mod check;
+pub mod crypt;
+pub mod ffi;
+pub mod fuzz;
+pub mod grid;
+pub mod hash;
+pub mod inputs;
+pub mod parallel;
+pub mod parse;
+pub mod registry;
+pub mod rng;
+pub mod runner;
+pub mod serve;
+pub mod stress;
+pub mod watch;
 
 aoc_lib!{ year = 2024 } 
\ No newline at end of file
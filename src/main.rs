@@ -1,8 +1,533 @@
-extern crate aoc_2024;
-extern crate aoc_runner_derive;
-extern crate aoc_runner;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
 
+use aoc_2024::{
+    alloc, check, crypt, day17, day18, day20, day24, fuzz, parallel, registry, runner, serve,
+    stress, watch,
+};
 
-use aoc_runner_derive::aoc_main;
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAlloc = alloc::CountingAlloc;
 
-aoc_main! {lib = aoc_2024}
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+const USAGE: &str = "usage (every command also takes --threads N or --single-threaded):
+  aoc-2024 [run] [--day N] [--part P] [--input FILE] [--timeout SECS]
+    day 18 also takes --memory SIZE,FALLEN, e.g. 7,12 for the puzzle's example,
+    and day 20 --thresholds PART1,PART2, e.g. 1,50
+  aoc-2024 run --all [--day N] [--budget MS] [--memory-budget MB] [--timeout SECS]
+  aoc-2024 check [--day N] [--part P] [--cases K] [--seed S] [--max-size M]
+  aoc-2024 fuzz [--day N] [--part P] [--cases K] [--seed S]
+  aoc-2024 gen-input --day N [--scale K] [--seed S] [--output FILE]  (days 1-14)
+  aoc-2024 bench --day N [--part P] [--scales K,K,..] [--seed S]     (days 1-14)
+  aoc-2024 watch --day N
+  aoc-2024 serve [--port PORT] [--timeout SECS]
+  aoc-2024 dot --day 24 [--input FILE] [--output FILE]
+  aoc-2024 trace --day 17 [--a N] [--input FILE]
+  aoc-2024 encrypt-inputs | decrypt-inputs | gen-key";
+
+#[derive(Debug, Default)]
+struct Args {
+    command: Option<String>,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    cases: Option<usize>,
+    seed: Option<u64>,
+    max_size: Option<usize>,
+    scale: Option<usize>,
+    scales: Option<Vec<usize>>,
+    output: Option<String>,
+    threads: Option<usize>,
+    all: bool,
+    budget_ms: Option<u64>,
+    memory_budget_mb: Option<usize>,
+    timeout_secs: Option<u64>,
+    port: Option<u16>,
+    register_a: Option<u64>,
+    memory: Option<(usize, usize)>,
+    thresholds: Option<(usize, usize)>,
+}
+
+impl Args {
+    fn selects(&self, day: u32, part: u32) -> bool {
+        self.day.is_none_or(|d| d == day) && self.part.is_none_or(|p| p == part)
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+        match arg.as_str() {
+            "run" | "check" | "fuzz" | "gen-input" | "bench" | "watch" | "encrypt-inputs"
+            | "decrypt-inputs" | "gen-key" | "serve" | "dot" | "trace"
+                if parsed.command.is_none() =>
+            {
+                parsed.command = Some(arg)
+            }
+            "--day" | "-d" => parsed.day = Some(parse_number(&value(&arg)?)?),
+            "--part" | "-p" => parsed.part = Some(parse_number(&value(&arg)?)?),
+            "--input" | "-i" => parsed.input = Some(value(&arg)?),
+            "--cases" => parsed.cases = Some(parse_number(&value(&arg)?)?),
+            "--seed" => parsed.seed = Some(parse_number(&value(&arg)?)?),
+            "--max-size" => parsed.max_size = Some(parse_number(&value(&arg)?)?),
+            "--scale" => parsed.scale = Some(parse_number(&value(&arg)?)?),
+            "--scales" => {
+                let scales: Result<_, _> = value(&arg)?.split(',').map(parse_number).collect();
+                parsed.scales = Some(scales?);
+            }
+            "--output" | "-o" => parsed.output = Some(value(&arg)?),
+            "--threads" => parsed.threads = Some(parse_number(&value(&arg)?)?),
+            "--single-threaded" => parsed.threads = Some(1),
+            "--all" | "-a" => parsed.all = true,
+            "--budget" => parsed.budget_ms = Some(parse_number(&value(&arg)?)?),
+            "--memory-budget" => parsed.memory_budget_mb = Some(parse_number(&value(&arg)?)?),
+            "--timeout" => parsed.timeout_secs = Some(parse_number(&value(&arg)?)?),
+            "--port" => parsed.port = Some(parse_number(&value(&arg)?)?),
+            "--a" => parsed.register_a = Some(parse_number(&value(&arg)?)?),
+            "--memory" => parsed.memory = Some(parse_pair(&value(&arg)?)?),
+            "--thresholds" => parsed.thresholds = Some(parse_pair(&value(&arg)?)?),
+            _ => return Err(format!("unknown argument {:?}", arg)),
+        }
+    }
+    if parsed.input.is_some() && parsed.day.is_none() {
+        return Err("--input needs --day".to_string());
+    }
+    let needs_day = matches!(
+        parsed.command.as_deref(),
+        Some("gen-input" | "bench" | "watch" | "dot" | "trace")
+    );
+    if needs_day && parsed.day.is_none() {
+        return Err(format!("{} needs --day", parsed.command.unwrap()));
+    }
+    Ok(parsed)
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{:?} is not a number", value))
+}
+
+fn parse_pair<T: std::str::FromStr>(value: &str) -> Result<(T, T), String> {
+    let (a, b) = value.split_once(',').ok_or(format!(
+        "{:?} is not two numbers separated by a comma",
+        value
+    ))?;
+    Ok((parse_number(a)?, parse_number(b)?))
+}
+
+fn load_input(args: &Args, day: u32) -> Option<String> {
+    let read = match &args.input {
+        Some(path) => fs::read_to_string(path),
+        None => runner::read_input(day),
+    };
+    if let Err(e) = &read {
+        eprintln!("Day {}: could not read input: {}", day, e);
+    }
+    read.ok()
+}
+
+fn run(args: &Args) -> ExitCode {
+    println!("Advent of code 2024");
+    let mut mismatch = false;
+    let mut timed_out = false;
+    let mut loaded: Option<(u32, Option<String>)> = None;
+    for (day, part) in registry::parts() {
+        if !args.selects(day, part) {
+            continue;
+        }
+        if loaded.as_ref().map(|(d, _)| *d) != Some(day) {
+            loaded = Some((day, load_input(args, day)));
+        }
+        let Some((_, Some(input))) = &loaded else {
+            continue;
+        };
+        let report = runner::run_part(day, part, input);
+        runner::print_report(&report);
+        mismatch |= report.is_mismatch();
+        timed_out |= report.timed_out();
+    }
+
+    exit_code(mismatch, timed_out)
+}
+
+fn exit_code(mismatch: bool, timed_out: bool) -> ExitCode {
+    if mismatch {
+        eprintln!("implementations disagree");
+    }
+    if timed_out {
+        eprintln!("some parts timed out");
+    }
+    if mismatch || timed_out {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// every selected part at once on a pool of workers, reports printed in day
+// order once all of them are done, then the timing summary
+fn run_all(args: &Args) -> ExitCode {
+    const DEFAULT_BUDGET_MS: u64 = 1000;
+
+    println!("Advent of code 2024");
+    let mut inputs = BTreeMap::new();
+    for (day, _) in registry::parts() {
+        if args.day.is_none_or(|d| d == day) && !inputs.contains_key(&day) {
+            inputs.insert(day, load_input(args, day));
+        }
+    }
+    let jobs: Vec<(u32, u32, &str)> = registry::parts()
+        .into_iter()
+        .filter(|&(day, part)| args.selects(day, part))
+        .filter_map(|(day, part)| Some((day, part, inputs[&day].as_deref()?)))
+        .collect();
+
+    // allocations are counted for the whole process, so peak memory is only
+    // per run, and a memory budget only checkable, one job at a time
+    let workers = match args.memory_budget_mb {
+        Some(_) => 1,
+        None => parallel::threads(),
+    };
+    let start = Instant::now();
+    let cpu_start = runner::cpu_time();
+    let reports = runner::run_concurrently(&jobs, workers);
+    let wall_clock = start.elapsed();
+    let cpu_time = cpu_start
+        .zip(runner::cpu_time())
+        .map(|(start, end)| end.saturating_sub(start));
+
+    for report in &reports {
+        runner::print_report(report);
+    }
+    let budget = runner::Budget {
+        time: Duration::from_millis(args.budget_ms.unwrap_or(DEFAULT_BUDGET_MS)),
+        memory: args.memory_budget_mb.map(|mb| mb << 20),
+    };
+    runner::print_summary(&reports, wall_clock, cpu_time, budget);
+
+    exit_code(
+        reports.iter().any(|report| report.is_mismatch()),
+        reports.iter().any(|report| report.timed_out()),
+    )
+}
+
+fn check(args: &Args) -> ExitCode {
+    let defaults = check::Config::default();
+    let config = check::Config {
+        cases: args.cases.unwrap_or(defaults.cases),
+        max_size: args.max_size.unwrap_or(defaults.max_size),
+        seed: args.seed.unwrap_or(defaults.seed),
+    };
+    let mut failed = false;
+    for property in check::PROPERTIES {
+        if !args.selects(property.day, property.part) {
+            continue;
+        }
+        let header = format!("Day {} - Part {}", property.day, property.part);
+        match check::check(property, config) {
+            Ok(()) => println!("{}: ok ({} cases)", header, config.cases),
+            Err(failure) => {
+                failed = true;
+                let shrunk = match failure.shrunk_from {
+                    Some(lines) => format!(", shrunk from a {}-line input", lines),
+                    None => String::new(),
+                };
+                println!(
+                    "{}: FAILED ({} implementation, seed {}, size {}{})",
+                    header,
+                    failure.solution.label(),
+                    failure.seed,
+                    failure.size,
+                    shrunk
+                );
+                println!("\texpected: {}", failure.expected);
+                match &failure.actual {
+                    Ok(answer) => println!("\tactual:   {}", answer),
+                    Err(e) => println!("\tactual:   error: {}", e),
+                }
+                println!("\tinput:\n{}", failure.input);
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fuzz(args: &Args) -> ExitCode {
+    let defaults = fuzz::Config::default();
+    let config = fuzz::Config {
+        cases: args.cases.unwrap_or(defaults.cases),
+        seed: args.seed.unwrap_or(defaults.seed),
+    };
+    let mut failed = false;
+    for (day, part) in registry::parts() {
+        if !args.selects(day, part) {
+            continue;
+        }
+        let header = format!("Day {} - Part {}", day, part);
+        if fuzz::is_skipped(day, part) {
+            println!("{}: skipped", header);
+            continue;
+        }
+        match fuzz::fuzz(day, part, config) {
+            Ok(()) => println!("{}: ok ({} cases)", header, config.cases),
+            Err(crash) => {
+                failed = true;
+                println!(
+                    "{}: CRASHED ({} implementation)",
+                    header,
+                    crash.solution.label()
+                );
+                println!("	{}", crash.message);
+                println!("	input: {:?}", crash.input);
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn no_generator(day: u32) {
+    let days = stress::days();
+    eprintln!(
+        "no input generator for day {}, there are only ones for days {} to {}",
+        day,
+        days.start(),
+        days.end()
+    );
+}
+
+fn gen_input(args: &Args) -> ExitCode {
+    let day = args.day.unwrap();
+    let seed = args.seed.unwrap_or(stress::DEFAULT_SEED);
+    let Some(input) = stress::generate(day, seed, args.scale.unwrap_or(1)) else {
+        no_generator(day);
+        return ExitCode::FAILURE;
+    };
+    let written = match &args.output {
+        Some(path) => fs::write(path, input),
+        None => io::stdout().write_all(input.as_bytes()),
+    };
+    if let Err(e) = written {
+        eprintln!("could not write input: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+// times every implementation on generated inputs of growing size, one row
+// per scale, so the shape of each curve shows how it grows
+fn bench(args: &Args) -> ExitCode {
+    const DEFAULT_SCALES: &[usize] = &[1, 2, 4, 8, 16];
+
+    let day = args.day.unwrap();
+    let seed = args.seed.unwrap_or(stress::DEFAULT_SEED);
+    let scales = args.scales.as_deref().unwrap_or(DEFAULT_SCALES);
+    let inputs: Option<Vec<String>> = scales
+        .iter()
+        .map(|&scale| stress::generate(day, seed, scale))
+        .collect();
+    let Some(inputs) = inputs else {
+        no_generator(day);
+        return ExitCode::FAILURE;
+    };
+    for (_, part) in registry::parts().into_iter().filter(|&(d, _)| d == day) {
+        if !args.selects(day, part) {
+            continue;
+        }
+        println!("Day {} - Part {}", day, part);
+        let labels: Vec<_> = registry::implementations(day, part)
+            .iter()
+            .map(|s| format!(" {:>12}", s.label()))
+            .collect();
+        println!("\t{:>6} {:>10}{}", "scale", "bytes", labels.join(""));
+        for (scale, input) in scales.iter().zip(&inputs) {
+            let report = runner::run_part(day, part, input);
+            let times: Vec<_> = report
+                .runs
+                .iter()
+                .map(|run| match &run.answer {
+                    Ok(_) => format!(" {:>12.2?}", run.elapsed),
+                    Err(_) => format!(" {:>12}", "failed"),
+                })
+                .collect();
+            println!("\t{:>6} {:>10}{}", scale, input.len(), times.join(""));
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+// encrypt-inputs and decrypt-inputs, over every input in runner::INPUT_DIR
+fn crypt_inputs(encrypt: bool) -> ExitCode {
+    let dir = Path::new(runner::INPUT_DIR);
+    let written = crypt::Key::load().and_then(|key| match encrypt {
+        true => crypt::encrypt_dir(dir, &key),
+        false => crypt::decrypt_dir(dir, &key),
+    });
+    match written {
+        Ok(written) => {
+            for path in &written {
+                println!("wrote {}", path.display());
+            }
+            println!("{} files written", written.len());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!(
+                "could not {}: {}",
+                if encrypt { "encrypt" } else { "decrypt" },
+                e
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn gen_key() -> ExitCode {
+    let created = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(crypt::KEY_FILE)
+        .and_then(|mut file| writeln!(file, "{}", crypt::generate_key_hex()));
+    match created {
+        Ok(()) => {
+            println!(
+                "wrote a new key to {}, keep a copy somewhere safe",
+                crypt::KEY_FILE
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("could not create {}: {}", crypt::KEY_FILE, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// day 24's circuit as a Graphviz graph, with the wires part 2 thinks were
+// swapped highlighted; the only day with a circuit to draw
+fn dot(args: &Args) -> ExitCode {
+    const DAY: u32 = 24;
+
+    if args.day != Some(DAY) {
+        eprintln!("only day {} has a circuit to draw", DAY);
+        return ExitCode::FAILURE;
+    }
+    let Some(input) = load_input(args, DAY) else {
+        return ExitCode::FAILURE;
+    };
+    let circuit = match day24::input_generator(&input) {
+        Ok(circuit) => circuit,
+        Err(e) => {
+            eprintln!("could not parse the circuit: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let dot = day24::to_dot(&circuit, &day24::find_swapped(&circuit));
+    let written = match &args.output {
+        Some(path) => fs::write(path, dot),
+        None => io::stdout().write_all(dot.as_bytes()),
+    };
+    if let Err(e) = written {
+        eprintln!("could not write the graph: {}", e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+// every instruction the day 17 program runs, with register A from --a or
+// else the input
+fn trace(args: &Args) -> ExitCode {
+    const DAY: u32 = 17;
+
+    if args.day != Some(DAY) {
+        eprintln!("only day {} has a program to trace", DAY);
+        return ExitCode::FAILURE;
+    }
+    let Some(input) = load_input(args, DAY) else {
+        return ExitCode::FAILURE;
+    };
+    let computer = match day17::input_generator(&input) {
+        Ok(computer) => computer,
+        Err(e) => {
+            eprintln!("could not parse the program: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match computer.trace(args.register_a.unwrap_or(computer.a)) {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("the program failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(threads) = args.threads {
+        parallel::set_threads(threads);
+    }
+    if let Some((size, fallen)) = args.memory {
+        day18::set_memory(day18::Memory { size, fallen });
+    }
+    if let Some((part1, part2)) = args.thresholds {
+        day20::set_thresholds(day20::Thresholds { part1, part2 });
+    }
+    // 0 turns the timeout off
+    let timeout = args.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+    runner::set_timeout((timeout > 0).then(|| Duration::from_secs(timeout)));
+    match args.command.as_deref() {
+        Some("check") => check(&args),
+        Some("fuzz") => fuzz(&args),
+        Some("gen-input") => gen_input(&args),
+        Some("bench") => bench(&args),
+        Some("watch") => match watch::watch(args.day.unwrap()) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("watch stopped: {}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Some("encrypt-inputs") => crypt_inputs(true),
+        Some("decrypt-inputs") => crypt_inputs(false),
+        Some("gen-key") => gen_key(),
+        Some("dot") => dot(&args),
+        Some("trace") => trace(&args),
+        Some("serve") => match serve::serve(args.port.unwrap_or(serve::DEFAULT_PORT)) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("server stopped: {}", e);
+                ExitCode::FAILURE
+            }
+        },
+        _ if args.all => run_all(&args),
+        _ => run(&args),
+    }
+}
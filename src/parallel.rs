@@ -0,0 +1,67 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+// worker threads for `map`, 0 means one per available core; set to 1 to run
+// everything on the calling thread, e.g. for steadier benchmark timings
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+// below this many items per thread splitting costs more than it saves
+const MIN_CHUNK: usize = 16;
+
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads, Ordering::Relaxed);
+}
+
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+}
+
+// `f` applied to every item, results in the same order as the items
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    map_init(items, || (), |_, item| f(item))
+}
+
+// like `map`, but every thread first builds scratch state with `init` and
+// hands it to each call, so buffers get reused instead of reallocated
+pub fn map_init<T, S, R, I, F>(items: &[T], init: I, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, &T) -> R + Sync,
+{
+    let workers = threads().min(items.len() / MIN_CHUNK).max(1);
+    let run_chunk = |chunk: &[T]| {
+        let mut state = init();
+        chunk
+            .iter()
+            .map(|item| f(&mut state, item))
+            .collect::<Vec<R>>()
+    };
+    if workers == 1 {
+        return run_chunk(items);
+    }
+    let chunk_size = items.len().div_ceil(workers);
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| run_chunk(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            // a panicking worker panics the caller with the same payload
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    })
+}
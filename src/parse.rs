@@ -0,0 +1,116 @@
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidNumber(String),
+    MissingSeparator {
+        line: String,
+        separator: char,
+    },
+    WrongCount {
+        line: String,
+        expected: usize,
+        found: usize,
+    },
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    Missing(&'static str),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidNumber(token) => write!(f, "invalid number {:?}", token),
+            Self::MissingSeparator { line, separator } => {
+                write!(f, "expected {:?} in {:?}", separator, line)
+            }
+            Self::WrongCount {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {} numbers in {:?}, found {}",
+                expected, line, found
+            ),
+            Self::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {} is {} wide, expected {}", row, found, expected),
+            Self::Missing(what) => write!(f, "no {} in the input", what),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    let text = text.trim();
+    text.parse()
+        .map_err(|_| ParseError::InvalidNumber(text.to_string()))
+}
+
+// every integer in the text, a '-' directly before the digits makes it negative
+pub fn ints<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let bytes = text.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+        if negative || bytes[i].is_ascii_digit() {
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            numbers.push(number(&text[start..i])?);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(numbers)
+}
+
+pub fn ints_exact<T: FromStr, const N: usize>(text: &str) -> Result<[T; N], ParseError> {
+    let numbers = ints(text)?;
+    let found = numbers.len();
+    numbers.try_into().map_err(|_| ParseError::WrongCount {
+        line: text.to_string(),
+        expected: N,
+        found,
+    })
+}
+
+pub fn key_value(line: &str, separator: char) -> Result<(&str, &str), ParseError> {
+    let (key, value) = line
+        .split_once(separator)
+        .ok_or_else(|| ParseError::MissingSeparator {
+            line: line.to_string(),
+            separator,
+        })?;
+    Ok((key.trim(), value.trim()))
+}
+
+// sections separated by one or more blank lines, without their surrounding newlines
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let mut start = None;
+        let mut end = offset;
+        for line in input[offset..].split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+            if !line.trim().is_empty() {
+                start.get_or_insert(line_start);
+                end = offset;
+            } else if start.is_some() {
+                break;
+            }
+        }
+        start.map(|start| input[start..end].trim_end_matches(['\n', '\r']))
+    })
+}
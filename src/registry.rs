@@ -0,0 +1,163 @@
+use std::error::Error;
+
+use crate::*;
+
+pub type SolveFn = fn(&str) -> Result<String, Box<dyn Error>>;
+
+// one implementation of a day/part, `name` is None for the main one and
+// e.g. Some("naive") for the alternatives that get cross-checked against it
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    pub name: Option<&'static str>,
+    pub solve: SolveFn,
+}
+
+impl Solution {
+    pub fn label(&self) -> &'static str {
+        self.name.unwrap_or("default")
+    }
+}
+
+// lets the registry accept generators that return their value directly as
+// well as ones that can fail
+pub trait Generated {
+    type Output;
+
+    fn into_result(self) -> Result<Self::Output, Box<dyn Error>>;
+}
+
+impl<T, E: Into<Box<dyn Error>>> Generated for Result<T, E> {
+    type Output = T;
+
+    fn into_result(self) -> Result<T, Box<dyn Error>> {
+        self.map_err(Into::into)
+    }
+}
+
+impl<T> Generated for Vec<T> {
+    type Output = Vec<T>;
+
+    fn into_result(self) -> Result<Vec<T>, Box<dyn Error>> {
+        Ok(self)
+    }
+}
+
+pub trait Answer {
+    fn into_answer(self) -> Result<String, Box<dyn Error>>;
+}
+
+macro_rules! display_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn into_answer(self) -> Result<String, Box<dyn Error>> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answer!(u8, u32, u64, u128, usize, i32, i64, i128, String);
+
+impl<T: Answer, E: Into<Box<dyn Error>>> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, Box<dyn Error>> {
+        self.map_err(Into::into)?.into_answer()
+    }
+}
+
+macro_rules! solution {
+    ($day:literal, $part:literal, $($name:literal,)? $generator:path => $solver:path) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: solution!(@name $($name)?),
+            solve: |input| {
+                let parsed = Generated::into_result($generator(input))?;
+                $solver(&parsed).into_answer()
+            },
+        }
+    };
+    ($day:literal, $part:literal, $($name:literal,)? $solver:path) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: solution!(@name $($name)?),
+            solve: |input| $solver(input).into_answer(),
+        }
+    };
+    (@name) => { None };
+    (@name $name:literal) => { Some($name) };
+}
+
+pub static SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, day1::input_generator => day1::part1),
+    solution!(1, 2, day1::input_generator => day1::part2),
+    solution!(1, 2, "naive", day1::input_generator => day1::part2_naive),
+    solution!(2, 1, day2::input_generator => day2::part1),
+    solution!(3, 1, day3::part1),
+    solution!(3, 2, day3::part2),
+    solution!(4, 1, day4::part1),
+    solution!(4, 2, day4::part2),
+    solution!(5, 1, day5::input_generator => day5::part1),
+    solution!(5, 2, day5::input_generator => day5::part2),
+    solution!(6, 1, day6::part1),
+    solution!(6, 2, day6::part2),
+    solution!(7, 1, day7::input_generator => day7::part1),
+    solution!(7, 1, "naive", day7::input_generator => day7::part1_naive),
+    solution!(7, 2, day7::input_generator => day7::part2),
+    solution!(7, 2, "naive", day7::input_generator => day7::part2_naive),
+    solution!(8, 1, day8::part1),
+    solution!(8, 2, day8::part2),
+    solution!(9, 1, day9::input_generator => day9::part1),
+    solution!(9, 2, day9::input_generator => day9::part2),
+    solution!(9, 2, "naive", day9::input_generator => day9::part2_naive),
+    solution!(10, 1, day10::part1),
+    solution!(10, 2, day10::part2),
+    solution!(11, 1, day11::input_generator => day11::part1),
+    solution!(11, 2, day11::input_generator => day11::part2),
+    solution!(12, 1, day12::part1),
+    solution!(12, 2, day12::part2),
+    solution!(13, 1, day13::input_generator => day13::part1),
+    solution!(13, 2, day13::input_generator => day13::part2),
+    solution!(14, 1, day14::input_generator => day14::part1),
+    solution!(14, 2, day14::input_generator => day14::part2_visualizer),
+    solution!(15, 1, day15::part1),
+    solution!(15, 2, day15::part2),
+    solution!(16, 1, day16::part1),
+    solution!(16, 2, day16::part2),
+    solution!(17, 1, day17::input_generator => day17::part1),
+    solution!(17, 2, day17::input_generator => day17::part2),
+    solution!(18, 1, day18::input_generator => day18::part1),
+    solution!(18, 2, day18::input_generator => day18::part2),
+    solution!(19, 1, day19::input_generator => day19::part1),
+    solution!(19, 2, day19::input_generator => day19::part2),
+    solution!(20, 1, day20::part1),
+    solution!(20, 2, day20::part2),
+    solution!(21, 1, day21::input_generator => day21::part1),
+    solution!(21, 2, day21::input_generator => day21::part2),
+    solution!(22, 1, day22::input_generator => day22::part1),
+    solution!(22, 2, day22::input_generator => day22::part2),
+    solution!(23, 1, day23::input_generator => day23::part1),
+    solution!(23, 2, day23::input_generator => day23::part2),
+    solution!(24, 1, day24::input_generator => day24::part1),
+    solution!(24, 2, day24::input_generator => day24::part2),
+];
+
+// every implementation of a day/part, the main one first
+pub fn implementations(day: u32, part: u32) -> Vec<&'static Solution> {
+    let mut found: Vec<_> = SOLUTIONS
+        .iter()
+        .filter(|s| s.day == day && s.part == part)
+        .collect();
+    found.sort_by_key(|s| s.name.is_some());
+    found
+}
+
+// (day, part) pairs that have at least one implementation, in order
+pub fn parts() -> Vec<(u32, u32)> {
+    let mut parts: Vec<_> = SOLUTIONS.iter().map(|s| (s.day, s.part)).collect();
+    parts.sort();
+    parts.dedup();
+    parts
+}
@@ -0,0 +1,345 @@
+use std::{
+    any::Any,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc, crypt,
+    registry::{self, Solution},
+};
+
+pub const INPUT_DIR: &str = "input/2024";
+
+pub fn input_path(day: u32) -> PathBuf {
+    PathBuf::from(INPUT_DIR).join(format!("day{}.txt", day))
+}
+
+// the plain dayN.txt when there is one, otherwise dayN.txt.enc decrypted
+// with the key from crypt::Key::load
+pub fn read_input(day: u32) -> io::Result<String> {
+    let path = input_path(day);
+    match fs::read_to_string(&path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let encrypted = crypt::encrypted_path(&path);
+            if !encrypted.exists() {
+                return Err(e);
+            }
+            crypt::Key::load()
+                .and_then(|key| crypt::read_encrypted(&encrypted, &key))
+                .map_err(io::Error::other)
+        }
+        read => read,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub solution: &'static Solution,
+    pub answer: Result<String, String>,
+    pub elapsed: Duration,
+    pub timed_out: bool,
+    // most bytes allocated at once while it ran, on top of what already was;
+    // None when other runs overlapped it, the counts are process-wide
+    pub peak_memory: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub day: u32,
+    pub part: u32,
+    pub runs: Vec<Run>,
+}
+
+impl PartReport {
+    // the answer every implementation agreed on, None if any failed or they differ
+    pub fn agreed_answer(&self) -> Option<&str> {
+        let first = self.runs.first()?.answer.as_ref().ok()?;
+        self.runs
+            .iter()
+            .all(|run| run.answer.as_ref() == Ok(first))
+            .then_some(first.as_str())
+    }
+
+    pub fn failed(&self) -> bool {
+        self.runs.iter().any(|run| run.answer.is_err())
+    }
+
+    pub fn timed_out(&self) -> bool {
+        self.runs.iter().any(|run| run.timed_out)
+    }
+
+    // every implementation produced an answer but they are not all the same
+    pub fn is_mismatch(&self) -> bool {
+        !self.failed() && self.agreed_answer().is_none()
+    }
+}
+
+// wall-clock limit for each implementation in milliseconds, 0 for none
+static TIMEOUT_MS: AtomicU64 = AtomicU64::new(0);
+
+pub fn set_timeout(timeout: Option<Duration>) {
+    let millis = timeout.map_or(0, |t| t.as_millis().max(1) as u64);
+    TIMEOUT_MS.store(millis, Ordering::Relaxed);
+}
+
+pub fn timeout() -> Option<Duration> {
+    match TIMEOUT_MS.load(Ordering::Relaxed) {
+        0 => None,
+        millis => Some(Duration::from_millis(millis)),
+    }
+}
+
+// threads of timed-out runs that are still going
+static ABANDONED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn abandoned() -> usize {
+    ABANDONED.load(Ordering::SeqCst)
+}
+
+// an abandoned run allocating in the background would show up in the peak,
+// so there is none while one is going
+fn run_measured(solution: &'static Solution, input: &str) -> Run {
+    let overlapped = abandoned() > 0;
+    let baseline = alloc::current();
+    alloc::reset_peak();
+    let start = Instant::now();
+    let answer = solve_catching(solution, input);
+    let elapsed = start.elapsed();
+    Run {
+        solution,
+        answer,
+        elapsed,
+        timed_out: false,
+        peak_memory: (!overlapped && abandoned() == 0)
+            .then(|| alloc::peak().saturating_sub(baseline)),
+    }
+}
+
+// with a timeout set the implementation runs on its own thread; if it is not
+// done in time that thread is abandoned, it keeps running in the background
+// until it finishes or the process exits, but the caller moves on
+pub fn run_solution(solution: &'static Solution, input: &str) -> Run {
+    let Some(limit) = timeout() else {
+        return run_measured(solution, input);
+    };
+    let (sender, receiver) = mpsc::channel();
+    let input: Arc<str> = Arc::from(input);
+    let start = Instant::now();
+    // set by the end of the run and by the timeout, whichever of the two
+    // comes second takes the thread back off the abandoned count
+    let settled = Arc::new(AtomicBool::new(false));
+    let thread_settled = settled.clone();
+    thread::spawn(move || {
+        // nobody is listening any more once it has timed out
+        let _ = sender.send(run_measured(solution, &input));
+        if thread_settled.swap(true, Ordering::SeqCst) {
+            ABANDONED.fetch_sub(1, Ordering::SeqCst);
+        }
+    });
+    receiver.recv_timeout(limit).unwrap_or_else(|_| {
+        ABANDONED.fetch_add(1, Ordering::SeqCst);
+        if settled.swap(true, Ordering::SeqCst) {
+            ABANDONED.fetch_sub(1, Ordering::SeqCst);
+        }
+        Run {
+            solution,
+            answer: Err(format!("timed out after {:?}", limit)),
+            elapsed: start.elapsed(),
+            timed_out: true,
+            // its own thread is still allocating
+            peak_memory: None,
+        }
+    })
+}
+
+// like calling `solution.solve` but a panic comes back as an error, with the
+// panic message when it had one; install a quiet panic hook around this to
+// keep the default one from printing
+pub fn solve_catching(solution: &Solution, input: &str) -> Result<String, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| (solution.solve)(input))) {
+        Ok(answer) => answer.map_err(|e| e.to_string()),
+        Err(payload) => Err(format!("panicked: {}", panic_message(&*payload))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown cause"
+    }
+}
+
+// runs every registered implementation of the part on the same input
+pub fn run_part(day: u32, part: u32, input: &str) -> PartReport {
+    let runs = registry::implementations(day, part)
+        .into_iter()
+        .map(|solution| run_solution(solution, input))
+        .collect();
+    PartReport { day, part, runs }
+}
+
+// runs only the part's main implementation, the first one registered
+pub fn run_main(day: u32, part: u32, input: &str) -> PartReport {
+    let runs = registry::implementations(day, part)
+        .into_iter()
+        .take(1)
+        .map(|solution| run_solution(solution, input))
+        .collect();
+    PartReport { day, part, runs }
+}
+
+pub fn print_report(report: &PartReport) {
+    let status = match report.agreed_answer() {
+        Some(answer) => answer,
+        None if report.timed_out() => "TIMED OUT",
+        None if report.failed() => "FAILED",
+        None => "MISMATCH",
+    };
+    println!("Day {} - Part {}: {}", report.day, report.part, status);
+    for run in &report.runs {
+        let label = run.solution.label();
+        let memory = format_memory(run.peak_memory);
+        match &run.answer {
+            Err(e) => println!("\t{:<10} failed: {}", label, e),
+            Ok(answer) if report.is_mismatch() => {
+                println!(
+                    "\t{:<10} {:>12?} {:>10}  {}",
+                    label, run.elapsed, memory, answer
+                )
+            }
+            Ok(_) => println!("\t{:<10} {:>12?} {:>10}", label, run.elapsed, memory),
+        }
+    }
+}
+
+fn format_memory(peak_memory: Option<usize>) -> String {
+    peak_memory.map_or("-".to_string(), alloc::format_bytes)
+}
+
+// runs every (day, part, input) job on `workers` threads that each take the
+// next unstarted job from a shared counter; reports come back in job order.
+// With more than one worker the runs overlap, so no peak memory is kept.
+pub fn run_concurrently(jobs: &[(u32, u32, &str)], workers: usize) -> Vec<PartReport> {
+    let next = AtomicUsize::new(0);
+    let worker = || {
+        let mut done = vec![];
+        loop {
+            let i = next.fetch_add(1, Ordering::Relaxed);
+            let Some(&(day, part, input)) = jobs.get(i) else {
+                break done;
+            };
+            done.push((i, run_part(day, part, input)));
+        }
+    };
+    let mut reports: Vec<(usize, PartReport)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers.max(1)).map(|_| scope.spawn(worker)).collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    reports.sort_by_key(|&(i, _)| i);
+    let mut reports: Vec<PartReport> = reports.into_iter().map(|(_, report)| report).collect();
+    if workers > 1 {
+        for run in reports.iter_mut().flat_map(|report| &mut report.runs) {
+            run.peak_memory = None;
+        }
+    }
+    reports
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    pub time: Duration,
+    // only checked for runs with a peak memory
+    pub memory: Option<usize>,
+}
+
+// user plus system CPU time the whole process has used so far, every
+// thread included; None where there is no getrusage
+#[cfg(unix)]
+pub fn cpu_time() -> Option<Duration> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let usage = unsafe { usage.assume_init() };
+    let duration = |time: libc::timeval| {
+        Duration::from_secs(time.tv_sec as u64) + Duration::from_micros(time.tv_usec as u64)
+    };
+    Some(duration(usage.ru_utime) + duration(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+pub fn cpu_time() -> Option<Duration> {
+    None
+}
+
+// one line per implementation, slowest first, then the totals. `cpu_time` is
+// what the process used over the whole run, so with several workers it comes
+// out higher than the wall-clock time; the summed runtime adds up every
+// implementation's own wall-clock time.
+pub fn print_summary(
+    reports: &[PartReport],
+    wall_clock: Duration,
+    cpu_time: Option<Duration>,
+    budget: Budget,
+) {
+    let mut runs: Vec<(u32, u32, &Run)> = reports
+        .iter()
+        .flat_map(|report| report.runs.iter().map(|run| (report.day, report.part, run)))
+        .collect();
+    runs.sort_by_key(|&(_, _, run)| std::cmp::Reverse(run.elapsed));
+
+    let memory_budget = budget
+        .memory
+        .map(|memory| format!(", {}", alloc::format_bytes(memory)))
+        .unwrap_or_default();
+    println!(
+        "Summary, slowest first (budget {:?}{})",
+        budget.time, memory_budget
+    );
+    for (day, part, run) in &runs {
+        let mut status = String::new();
+        if run.timed_out {
+            status += "  TIMED OUT";
+        } else if run.answer.is_err() {
+            status += "  FAILED";
+        } else if run.elapsed > budget.time {
+            status += "  OVER BUDGET";
+        }
+        if let (Some(peak), Some(limit)) = (run.peak_memory, budget.memory)
+            && peak > limit
+        {
+            status += "  OVER MEMORY BUDGET";
+        }
+        println!(
+            "\tDay {:>2} - Part {} {:<10} {:>12.2?} {:>10}{}",
+            day,
+            part,
+            run.solution.label(),
+            run.elapsed,
+            format_memory(run.peak_memory),
+            status
+        );
+    }
+    let summed: Duration = runs.iter().map(|(_, _, run)| run.elapsed).sum();
+    println!("\t{:<26} {:>12.2?}", "wall-clock", wall_clock);
+    match cpu_time {
+        Some(cpu_time) => println!("\t{:<26} {:>12.2?}", "cpu time", cpu_time),
+        None => println!("\t{:<26} {:>12}", "cpu time", "unavailable"),
+    }
+    println!("\t{:<26} {:>12.2?}", "summed runtime", summed);
+}
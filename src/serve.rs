@@ -0,0 +1,331 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, TrySendError},
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    registry,
+    runner::{self, PartReport},
+};
+
+// a small HTTP/1.1 server on localhost only; the routes are
+//   GET  /2024                   the parts that can be solved
+//   POST /2024/day/N             both parts of day N on the request body
+//   POST /2024/day/N/part/P      just part P
+// answers come back as JSON with the main implementation's result and
+// timing, or every implementation's with ?implementations=all on the POST.
+// Connections are handled by a fixed pool of threads and closed after one
+// response; with every thread busy and the queue full a new one gets a 503,
+// as does a POST while a timed-out solver is still running.
+pub const DEFAULT_PORT: u16 = 2024;
+
+const WORKERS: usize = 4;
+const QUEUED_CONNECTIONS: usize = 16;
+
+// day 14 part 2 writes every frame to disk as a PNG, not something a
+// request should set off
+const REFUSED: &[(u32, u32)] = &[(14, 2)];
+
+const MAX_HEADER_BYTES: usize = 16 << 10;
+// the largest real input is about 25 KiB
+const MAX_BODY_BYTES: usize = 1 << 20;
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(body: String) -> Self {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        let body = format!("{{\"error\":{}}}", json_string(message));
+        Response { status, body }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+pub fn serve(port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    println!("listening on http://{}", listener.local_addr()?);
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(QUEUED_CONNECTIONS);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..WORKERS {
+        let receiver = receiver.clone();
+        thread::spawn(move || {
+            loop {
+                // the lock is only held while waiting for the next connection
+                let Ok(stream) = receiver.lock().unwrap().recv() else {
+                    break;
+                };
+                if let Err(e) = handle(stream) {
+                    eprintln!("connection failed: {}", e);
+                }
+            }
+        });
+    }
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("could not accept a connection: {}", e);
+                continue;
+            }
+        };
+        match sender.try_send(stream) {
+            Ok(()) => {}
+            Err(TrySendError::Full(stream) | TrySendError::Disconnected(stream)) => {
+                let busy = Response::error(503, "too many connections, try again later");
+                if let Err(e) = respond(&stream, &busy) {
+                    eprintln!("connection failed: {}", e);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn handle(stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader)? {
+        Ok(request) => route(&request),
+        Err(response) => response,
+    };
+    respond(&stream, &response)
+}
+
+fn respond(stream: &TcpStream, response: &Response) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut writer = stream;
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    writer.flush()
+}
+
+// the outer error is the connection failing, the inner one a request that
+// gets an error response
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let mut head = vec![];
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader
+            .by_ref()
+            .take((MAX_HEADER_BYTES + 1) as u64)
+            .read_line(&mut line)?;
+        if read == 0 {
+            return Ok(Err(Response::error(400, "connection closed mid-request")));
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        head.push(line.to_string());
+        if head.iter().map(String::len).sum::<usize>() > MAX_HEADER_BYTES {
+            return Ok(Err(Response::error(400, "headers too large")));
+        }
+    }
+
+    let mut request_line = head.first().map_or("", String::as_str).split(' ');
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Ok(Err(Response::error(400, "malformed request line")));
+    };
+    let content_length = head[1..]
+        .iter()
+        .filter_map(|header| header.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .map(|(_, value)| value.trim().parse::<usize>());
+    let length = match content_length {
+        None => 0,
+        Some(Ok(length)) if length <= MAX_BODY_BYTES => length,
+        Some(Ok(_)) => return Ok(Err(Response::error(413, "input too large"))),
+        Some(Err(_)) => return Ok(Err(Response::error(400, "bad Content-Length"))),
+    };
+    // read as it arrives rather than trusting Content-Length up front
+    let mut body = vec![];
+    reader.by_ref().take(length as u64).read_to_end(&mut body)?;
+    if body.len() < length {
+        return Ok(Err(Response::error(400, "connection closed mid-body")));
+    }
+    Ok(Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    }))
+}
+
+fn served_parts() -> Vec<(u32, u32)> {
+    registry::parts()
+        .into_iter()
+        .filter(|part| !REFUSED.contains(part))
+        .collect()
+}
+
+fn route(request: &Request) -> Response {
+    let (path, query) = request.path.split_once('?').unwrap_or((&request.path, ""));
+    let all_implementations = query.split('&').any(|pair| pair == "implementations=all");
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let (day, part) = match segments[..] {
+        ["2024"] => {
+            return match request.method.as_str() {
+                "GET" => Response::json(parts_json()),
+                _ => Response::error(405, "use GET"),
+            };
+        }
+        ["2024", "day", day] => (day, None),
+        ["2024", "day", day, "part", part] => (day, Some(part)),
+        _ => return Response::error(404, "no such route"),
+    };
+    if request.method != "POST" {
+        return Response::error(405, "use POST with the puzzle input as the body");
+    }
+    let (Ok(day), Ok(part)) = (day.parse::<u32>(), part.map(str::parse::<u32>).transpose()) else {
+        return Response::error(404, "day and part must be numbers");
+    };
+    if let Some(part) = part
+        && REFUSED.contains(&(day, part))
+    {
+        return Response::error(403, "that part writes files to disk, it is not served");
+    }
+    let parts: Vec<u32> = served_parts()
+        .into_iter()
+        .filter(|&(d, p)| d == day && part.is_none_or(|part| part == p))
+        .map(|(_, p)| p)
+        .collect();
+    if parts.is_empty() {
+        return Response::error(404, "no implementation for that day and part");
+    }
+    // an abandoned solver keeps its thread busy until it finishes
+    if runner::abandoned() > 0 {
+        return Response::error(503, "a timed-out solver is still running, try again later");
+    }
+    let Ok(input) = std::str::from_utf8(&request.body) else {
+        return Response::error(400, "input is not UTF-8");
+    };
+
+    let reports: Vec<String> = parts
+        .into_iter()
+        .map(|part| {
+            let report = match all_implementations {
+                true => runner::run_part(day, part, input),
+                false => runner::run_main(day, part, input),
+            };
+            report_json(&report)
+        })
+        .collect();
+    match (part, &reports[..]) {
+        (Some(_), [report]) => Response::json(report.clone()),
+        _ => Response::json(format!(
+            "{{\"day\":{},\"parts\":[{}]}}",
+            day,
+            reports.join(",")
+        )),
+    }
+}
+
+fn parts_json() -> String {
+    let parts: Vec<String> = served_parts()
+        .into_iter()
+        .map(|(day, part)| {
+            let names: Vec<String> = registry::implementations(day, part)
+                .iter()
+                .map(|s| json_string(s.label()))
+                .collect();
+            format!(
+                "{{\"day\":{},\"part\":{},\"implementations\":[{}]}}",
+                day,
+                part,
+                names.join(",")
+            )
+        })
+        .collect();
+    format!("{{\"year\":2024,\"parts\":[{}]}}", parts.join(","))
+}
+
+// "answer" is null unless every implementation agreed on it; peak memory is
+// measured process-wide, so requests running at the same time inflate it
+fn report_json(report: &PartReport) -> String {
+    let runs: Vec<String> = report
+        .runs
+        .iter()
+        .map(|run| {
+            let (answer, error) = match &run.answer {
+                Ok(answer) => (json_string(answer), "null".to_string()),
+                Err(e) => ("null".to_string(), json_string(e)),
+            };
+            format!(
+                "{{\"name\":{},\"answer\":{},\"error\":{},\"elapsed_us\":{},\"peak_memory_bytes\":{},\"timed_out\":{}}}",
+                json_string(run.solution.label()),
+                answer,
+                error,
+                run.elapsed.as_micros(),
+                run.peak_memory
+                    .map_or("null".to_string(), |peak| peak.to_string()),
+                run.timed_out
+            )
+        })
+        .collect();
+    format!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"mismatch\":{},\"implementations\":[{}]}}",
+        report.day,
+        report.part,
+        report
+            .agreed_answer()
+            .map_or("null".to_string(), json_string),
+        report.is_mismatch(),
+        runs.join(",")
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,316 @@
+use std::{collections::VecDeque, fmt::Write, ops::RangeInclusive};
+
+use crate::{
+    inputs::{guard_walk, to_lines},
+    rng::Rng,
+};
+
+// deterministic puzzle-valid inputs shaped like the real ones but `scale`
+// times their size: scale 1 is about a real input, grids grow both sides by
+// sqrt(scale) so the cell count scales linearly too
+
+pub const DEFAULT_SEED: u64 = 2024;
+
+pub fn generate(day: u32, seed: u64, scale: usize) -> Option<String> {
+    let rng = &mut Rng::new(seed ^ (day as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    let scale = scale.max(1);
+    let input = match day {
+        1 => day1(rng, 1000 * scale),
+        2 => day2(rng, 1000 * scale),
+        3 => day3(rng, 6 * scale),
+        4 => day4(rng, side(140, scale)),
+        5 => day5(rng, 186 * scale),
+        6 => day6(rng, side(130, scale)),
+        7 => day7(rng, 850 * scale),
+        8 => day8(rng, side(50, scale), 200 * scale),
+        9 => day9(rng, 10_000 * scale),
+        10 => day10(rng, side(40, scale)),
+        11 => day11(rng, 8 * scale),
+        12 => day12(rng, side(140, scale)),
+        13 => day13(rng, 320 * scale),
+        14 => day14(rng, 500 * scale),
+        _ => return None,
+    };
+    Some(input)
+}
+
+// the days `generate` has an input generator for
+pub fn days() -> RangeInclusive<u32> {
+    1..=14
+}
+
+fn side(base: usize, scale: usize) -> usize {
+    (base as f64 * (scale as f64).sqrt()).round() as usize
+}
+
+fn shuffle<T>(rng: &mut Rng, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.index(i + 1));
+    }
+}
+
+fn day1(rng: &mut Rng, lines: usize) -> String {
+    let left: Vec<i64> = (0..lines).map(|_| rng.range(10000..=99999)).collect();
+    let mut input = String::new();
+    for &first in &left {
+        // about half the right column repeats the left one so part 2 has
+        // similarity scores to add up
+        let second = match rng.chance(50) {
+            true => *rng.pick(&left),
+            false => rng.range(10000..=99999),
+        };
+        writeln!(input, "{}   {}", first, second).unwrap();
+    }
+    input
+}
+
+fn day2(rng: &mut Rng, reports: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..reports {
+        let len = 5 + rng.index(4);
+        let sign = if rng.chance(50) { 1 } else { -1 };
+        let mut level = rng.range(20..=70);
+        let mut levels = vec![];
+        for _ in 0..len {
+            levels.push(level.to_string());
+            // mostly safe steps, sometimes a flat or too large one
+            let step = if rng.chance(90) {
+                rng.range(1..=3)
+            } else {
+                rng.range(0..=5)
+            };
+            level += sign * step;
+        }
+        writeln!(input, "{}", levels.join(" ")).unwrap();
+    }
+    input
+}
+
+fn day3(rng: &mut Rng, lines: usize) -> String {
+    const NOISE: &[&str] = &[
+        "who()", "what()", "from()", "select()", "when()", "where()", "#", "!", "[", "]", "<", ">",
+        "{", "}", "'", "mul(", "mul[3,4]", "mul(4*", "?(12,34)", " ",
+    ];
+    let mut input = String::new();
+    for _ in 0..lines {
+        let mut line = String::new();
+        while line.len() < 3000 {
+            match rng.below(10) {
+                0..4 => write!(line, "mul({},{})", rng.range(1..=999), rng.range(1..=999)).unwrap(),
+                4 => line.push_str("do()"),
+                5 => line.push_str("don't()"),
+                _ => line.push_str(rng.pick::<&str>(NOISE)),
+            }
+        }
+        input.push_str(&line);
+        input.push('\n');
+    }
+    input
+}
+
+fn day4(rng: &mut Rng, side: usize) -> String {
+    let grid: Vec<Vec<u8>> = (0..side)
+        .map(|_| (0..side).map(|_| *rng.pick(b"XMAS")).collect())
+        .collect();
+    to_lines(&grid)
+}
+
+fn day5(rng: &mut Rng, updates: usize) -> String {
+    // one hidden total order over the pages; every rule and every correctly
+    // ordered update agrees with it, so sorting by the rules is well defined
+    let mut pages: Vec<i64> = (10..=99).collect();
+    shuffle(rng, &mut pages);
+    let pages = &pages[..49];
+    let mut input = String::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            writeln!(input, "{}|{}", before, after).unwrap();
+        }
+    }
+    input.push('\n');
+    for _ in 0..updates {
+        let len = 5 + 2 * rng.index(10);
+        let mut picked: Vec<usize> = (0..pages.len()).collect();
+        shuffle(rng, &mut picked);
+        picked.truncate(len);
+        if rng.chance(50) {
+            picked.sort();
+        }
+        let update: Vec<String> = picked.iter().map(|&i| pages[i].to_string()).collect();
+        writeln!(input, "{}", update.join(",")).unwrap();
+    }
+    input
+}
+
+fn day6(rng: &mut Rng, side: usize) -> String {
+    // random maps mostly let the guard walk straight off, so the longest walk
+    // out of a few tries is kept to get closer to the real ones
+    const TRIES: usize = 20;
+
+    let mut longest: Option<(usize, Vec<Vec<u8>>)> = None;
+    let mut tries = 0;
+    while tries < TRIES || longest.is_none() {
+        tries += 1;
+        let mut grid: Vec<Vec<u8>> = (0..side)
+            .map(|_| {
+                (0..side)
+                    .map(|_| if rng.chance(5) { b'#' } else { b'.' })
+                    .collect()
+            })
+            .collect();
+        let start = (side / 2 + rng.index(side.div_ceil(2)), rng.index(side));
+        grid[start.0][start.1] = b'^';
+        if let Some(steps) = guard_walk(&grid, start)
+            && longest.as_ref().is_none_or(|(best, _)| steps > *best)
+        {
+            longest = Some((steps, grid));
+        }
+    }
+    to_lines(&longest.unwrap().1)
+}
+
+fn day7(rng: &mut Rng, equations: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..equations {
+        let count = 3 + rng.index(10);
+        let nums: Vec<u128> = (0..count).map(|_| rng.range(1..=999) as u128).collect();
+        let target = nums[1..]
+            .iter()
+            .fold(nums[0], |acc, &n| match rng.below(3) {
+                0 => acc + n,
+                // keeps the targets in the range of the real ones
+                1 if acc.saturating_mul(n) < 1 << 50 => acc * n,
+                _ => acc + n + rng.below(2) as u128,
+            });
+        let nums: Vec<String> = nums.iter().map(u128::to_string).collect();
+        writeln!(input, "{}: {}", target, nums.join(" ")).unwrap();
+    }
+    input
+}
+
+fn day8(rng: &mut Rng, side: usize, antennas: usize) -> String {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut grid = vec![vec![b'.'; side]; side];
+    for _ in 0..antennas.min(side * side / 4) {
+        let (r, c) = (rng.index(side), rng.index(side));
+        grid[r][c] = *rng.pick(FREQUENCIES);
+    }
+    to_lines(&grid)
+}
+
+fn day9(rng: &mut Rng, files: usize) -> String {
+    let mut input = String::new();
+    for file in 0..files {
+        input.push(char::from(b'1' + rng.below(9) as u8));
+        if file + 1 < files {
+            input.push(char::from(b'0' + rng.below(10) as u8));
+        }
+    }
+    input.push('\n');
+    input
+}
+
+fn day10(rng: &mut Rng, side: usize) -> String {
+    // random peaks with heights falling off by distance around them, so
+    // there are long trails like in the real maps
+    let mut distance = vec![vec![usize::MAX; side]; side];
+    let mut queue = VecDeque::new();
+    for _ in 0..1 + side * side / 100 {
+        let peak = (rng.index(side), rng.index(side));
+        distance[peak.0][peak.1] = 0;
+        queue.push_back(peak);
+    }
+    while let Some((r, c)) = queue.pop_front() {
+        let next = [
+            (r + 1, c),
+            (r, c + 1),
+            (r.wrapping_sub(1), c),
+            (r, c.wrapping_sub(1)),
+        ];
+        for (nr, nc) in next {
+            if nr < side && nc < side && distance[nr][nc] == usize::MAX {
+                distance[nr][nc] = distance[r][c] + 1;
+                queue.push_back((nr, nc));
+            }
+        }
+    }
+    let grid: Vec<Vec<u8>> = distance
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&d| {
+                    let noise = if rng.chance(10) { rng.range(-1..=1) } else { 0 };
+                    b'0' + (9 - d.min(9) as i64 + noise).clamp(0, 9) as u8
+                })
+                .collect()
+        })
+        .collect();
+    to_lines(&grid)
+}
+
+fn day11(rng: &mut Rng, stones: usize) -> String {
+    let stones: Vec<String> = (0..stones)
+        .map(|_| rng.range(0..=9_999_999).to_string())
+        .collect();
+    stones.join(" ") + "\n"
+}
+
+fn day12(rng: &mut Rng, side: usize) -> String {
+    let plants: Vec<u8> = (b'A'..=b'Z').collect();
+    let mut grid = vec![vec![b'A'; side]; side];
+    for r in 0..side {
+        for c in 0..side {
+            // copying a neighbour most of the time grows regions of the size
+            // the real gardens have
+            grid[r][c] = match rng.below(20) {
+                0..9 if r > 0 => grid[r - 1][c],
+                9..18 if c > 0 => grid[r][c - 1],
+                _ => *rng.pick(&plants),
+            };
+        }
+    }
+    to_lines(&grid)
+}
+
+fn day13(rng: &mut Rng, machines: usize) -> String {
+    let mut blocks = vec![];
+    for _ in 0..machines {
+        let (a, b) = loop {
+            let a = (rng.range(10..=99), rng.range(10..=99));
+            let b = (rng.range(10..=99), rng.range(10..=99));
+            if a.0 * b.1 != a.1 * b.0 {
+                break (a, b);
+            }
+        };
+        let prize = if rng.chance(50) {
+            let (presses_a, presses_b) = (rng.range(1..=100), rng.range(1..=100));
+            (
+                a.0 * presses_a + b.0 * presses_b,
+                a.1 * presses_a + b.1 * presses_b,
+            )
+        } else {
+            (rng.range(1000..=20000), rng.range(1000..=20000))
+        };
+        blocks.push(format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a.0, a.1, b.0, b.1, prize.0, prize.1
+        ));
+    }
+    blocks.join("\n")
+}
+
+fn day14(rng: &mut Rng, robots: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..robots {
+        writeln!(
+            input,
+            "p={},{} v={},{}",
+            rng.range(0..=100),
+            rng.range(0..=102),
+            rng.range(-100..=100),
+            rng.range(-100..=100)
+        )
+        .unwrap();
+    }
+    input
+}
@@ -0,0 +1,169 @@
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{crypt, day18, day20, runner};
+
+// example inputs from the puzzle text live next to the real ones as
+// input/2024/examples/dayN.txt, or dayN-<name>.txt when a day has several
+pub const EXAMPLES_DIR: &str = "input/2024/examples";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn examples(day: u32) -> Vec<PathBuf> {
+    let single = format!("day{}.txt", day);
+    let prefix = format!("day{}-", day);
+    let mut found: Vec<PathBuf> = fs::read_dir(EXAMPLES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name == single || (name.starts_with(&prefix) && name.ends_with(".txt"))
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+pub fn watched_files(day: u32) -> Vec<PathBuf> {
+    let input = runner::input_path(day);
+    let mut files = vec![
+        PathBuf::from(format!("src/day{}.rs", day)),
+        crypt::encrypted_path(&input),
+        input,
+    ];
+    files.extend(examples(day));
+    files
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// answers keyed by (input name, part)
+type Answers = BTreeMap<(String, u32), String>;
+
+// rebuilds the binary with the profile this one was built with; cargo's own
+// output goes straight to the terminal so compile errors show up as usual
+fn rebuild() -> io::Result<bool> {
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or("cargo".to_string()));
+    cargo.args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    Ok(cargo.status()?.success())
+}
+
+// what the examples need on the command line where the puzzle text works
+// them out with other parameters than the real input
+fn example_args(day: u32) -> Vec<String> {
+    match day {
+        18 => {
+            let memory = day18::Memory::EXAMPLE;
+            vec![
+                "--memory".to_string(),
+                format!("{},{}", memory.size, memory.fallen),
+            ]
+        }
+        20 => {
+            let thresholds = day20::Thresholds::EXAMPLE;
+            vec![
+                "--thresholds".to_string(),
+                format!("{},{}", thresholds.part1, thresholds.part2),
+            ]
+        }
+        _ => vec![],
+    }
+}
+
+fn run_input(exe: &Path, day: u32, input: Option<&Path>) -> io::Result<BTreeMap<u32, String>> {
+    let mut command = Command::new(exe);
+    command.args(["run", "--day", &day.to_string()]);
+    if let Some(path) = input {
+        command.arg("--input").arg(path).args(example_args(day));
+    }
+    let output = command.stderr(Stdio::null()).output()?;
+    Ok(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// picks the "Day N - Part P: answer" lines out of a run's output
+fn parse_answers(output: &str) -> BTreeMap<u32, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.strip_prefix("Day ")?.split_once(" - Part ")?;
+            let (part, answer) = rest.split_once(": ")?;
+            Some((part.parse().ok()?, answer.to_string()))
+        })
+        .collect()
+}
+
+fn run_all_inputs(exe: &Path, day: u32) -> io::Result<Answers> {
+    let mut answers = Answers::new();
+    for example in examples(day) {
+        let name = example.file_name().unwrap().to_string_lossy().to_string();
+        for (part, answer) in run_input(exe, day, Some(&example))? {
+            answers.insert((name.clone(), part), answer);
+        }
+    }
+    for (part, answer) in run_input(exe, day, None)? {
+        answers.insert(("input".to_string(), part), answer);
+    }
+    Ok(answers)
+}
+
+fn print_diff(day: u32, previous: &Answers, current: &Answers) {
+    for ((name, part), answer) in current {
+        let change = match previous.get(&(name.clone(), *part)) {
+            None => String::new(),
+            Some(old) if old == answer => "  (unchanged)".to_string(),
+            Some(old) => format!("  (was {})", old),
+        };
+        println!(
+            "Day {} - Part {} [{}]: {}{}",
+            day, part, name, answer, change
+        );
+    }
+    if current.is_empty() {
+        println!("Day {}: no answers", day);
+    }
+}
+
+// never returns unless watching itself fails; each change to a watched file
+// rebuilds, reruns the examples and the real input and prints the answers
+// next to the ones from the run before
+pub fn watch(day: u32) -> io::Result<()> {
+    // looked up once: after a rebuild replaces the binary, asking again gives
+    // the path of the deleted old one on Linux
+    let exe = env::current_exe()?;
+    let mut last_seen = vec![];
+    let mut previous = Answers::new();
+    loop {
+        let files = watched_files(day);
+        let seen: Vec<_> = files.iter().map(|f| (f.clone(), modified(f))).collect();
+        if seen != last_seen {
+            let changed: Vec<String> = seen
+                .iter()
+                .filter(|entry| !last_seen.contains(entry))
+                .map(|(path, _)| path.display().to_string())
+                .collect();
+            println!("---- changed: {}", changed.join(", "));
+            last_seen = seen;
+            if rebuild()? {
+                let current = run_all_inputs(&exe, day)?;
+                print_diff(day, &previous, &current);
+                previous = current;
+            } else {
+                println!("build failed, waiting for the next change");
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,27 @@
+// the differential checks from `aoc-2024 check` at a fixed seed, so a
+// failure here reproduces with `aoc-2024 check --day N --part P --seed 2024`
+
+use aoc_2024::check::{self, Config, PROPERTIES};
+
+#[test]
+fn implementations_match_references() {
+    let config = Config {
+        seed: 2024,
+        ..Config::default()
+    };
+    for property in PROPERTIES {
+        if let Err(failure) = check::check(property, config) {
+            panic!(
+                "day {} part {}: {} implementation gave {:?} instead of {:?} at seed {}, size {} on\n{}",
+                property.day,
+                property.part,
+                failure.solution.label(),
+                failure.actual,
+                failure.expected,
+                failure.seed,
+                failure.size,
+                failure.input
+            );
+        }
+    }
+}
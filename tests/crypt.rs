@@ -0,0 +1,36 @@
+// inputs are committed encrypted, so what goes in has to come back out, and
+// anything else has to be turned away rather than decrypted into garbage
+
+use aoc_2024::crypt::{self, Key};
+
+const PLAINTEXT: &[u8] = b"3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+fn new_key() -> Key {
+    Key::from_hex(&crypt::generate_key_hex()).unwrap()
+}
+
+#[test]
+fn decrypts_what_it_encrypted() {
+    let key = new_key();
+    let data = key.encrypt(PLAINTEXT);
+    assert_ne!(&data[..], PLAINTEXT);
+    assert_eq!(key.decrypt(&data).as_deref(), Some(PLAINTEXT));
+}
+
+#[test]
+fn rejects_tampered_data() {
+    let key = new_key();
+    let data = key.encrypt(PLAINTEXT);
+    for i in 0..data.len() {
+        let mut tampered = data.clone();
+        tampered[i] ^= 1;
+        assert_eq!(key.decrypt(&tampered), None, "byte {} flipped", i);
+    }
+    assert_eq!(key.decrypt(&data[..data.len() - 1]), None);
+}
+
+#[test]
+fn rejects_another_key() {
+    let data = new_key().encrypt(PLAINTEXT);
+    assert_eq!(new_key().decrypt(&data), None);
+}
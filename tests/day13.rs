@@ -0,0 +1,31 @@
+// machines whose buttons move along the same line have many ways to the
+// prize; part 1 wants the cheapest one within its 100 presses per button
+
+use aoc_2024::day13;
+
+#[test]
+fn parallel_buttons_cheapest_within_limit() {
+    // the cheapest overall is 1 A and 153 B, too many B presses for part 1,
+    // which has to settle for 37 A and 99 B
+    let machines =
+        day13::input_generator("Button A: X+9, Y+36\nButton B: X+6, Y+24\nPrize: X=927, Y=3708\n")
+            .unwrap();
+    assert_eq!(day13::part1(&machines), 210);
+}
+
+#[test]
+fn parallel_buttons_off_the_line() {
+    let machines =
+        day13::input_generator("Button A: X+9, Y+36\nButton B: X+6, Y+24\nPrize: X=927, Y=3709\n")
+            .unwrap();
+    assert_eq!(day13::part1(&machines), 0);
+}
+
+#[test]
+fn buttons_that_do_not_move() {
+    let machines =
+        day13::input_generator("Button A: X+0, Y+0\nButton B: X+0, Y+0\nPrize: X=0, Y=0\n")
+            .unwrap();
+    assert_eq!(day13::part1(&machines), 0);
+    assert_eq!(day13::part2(&machines), 0);
+}
@@ -0,0 +1,20 @@
+// the puzzle text's examples: the larger warehouse, the small one, and the
+// small one made to widen
+
+use aoc_2024::day15;
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day15.txt");
+const SMALL: &str = include_str!("../input/2024/examples/day15-small.txt");
+const WIDE: &str = include_str!("../input/2024/examples/day15-wide.txt");
+
+#[test]
+fn example_gps_sum() {
+    assert_eq!(day15::part1(EXAMPLE).unwrap(), 10092);
+    assert_eq!(day15::part1(SMALL).unwrap(), 2028);
+}
+
+#[test]
+fn example_wide_gps_sum() {
+    assert_eq!(day15::part2(EXAMPLE).unwrap(), 9021);
+    assert_eq!(day15::part2(WIDE).unwrap(), 618);
+}
@@ -0,0 +1,18 @@
+// the puzzle text's two example mazes
+
+use aoc_2024::day16;
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day16.txt");
+const SECOND: &str = include_str!("../input/2024/examples/day16-second.txt");
+
+#[test]
+fn example_lowest_score() {
+    assert_eq!(day16::part1(EXAMPLE).unwrap(), 7036);
+    assert_eq!(day16::part1(SECOND).unwrap(), 11048);
+}
+
+#[test]
+fn example_tiles_on_best_paths() {
+    assert_eq!(day16::part2(EXAMPLE).unwrap(), 45);
+    assert_eq!(day16::part2(SECOND).unwrap(), 64);
+}
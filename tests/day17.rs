@@ -0,0 +1,40 @@
+// the instruction trace `aoc-2024 trace --day 17` prints, on the example
+// program from the puzzle text
+
+use aoc_2024::day17;
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day17.txt");
+
+#[test]
+fn traces_example_program() {
+    let computer = day17::input_generator(EXAMPLE).unwrap();
+    let trace = computer.trace(computer.a).unwrap();
+    // A is shifted right once per loop of three instructions until it is 0
+    assert_eq!(trace.len(), 30);
+    assert_eq!(
+        trace[..4],
+        [
+            "  0: adv 1  A=729 B=0 C=0",
+            "  2: out 4  A=364 B=0 C=0",
+            "  4: jnz 0  A=364 B=0 C=0",
+            "  0: adv 1  A=364 B=0 C=0",
+        ]
+    );
+    assert_eq!(trace[29], "  4: jnz 0  A=0 B=0 C=0");
+}
+
+#[test]
+fn traces_with_another_register_a() {
+    let computer = day17::input_generator(EXAMPLE).unwrap();
+    assert_eq!(
+        computer.trace(2).unwrap(),
+        [
+            "  0: adv 1  A=2 B=0 C=0",
+            "  2: out 4  A=1 B=0 C=0",
+            "  4: jnz 0  A=1 B=0 C=0",
+            "  0: adv 1  A=1 B=0 C=0",
+            "  2: out 4  A=0 B=0 C=0",
+            "  4: jnz 0  A=0 B=0 C=0",
+        ]
+    );
+}
@@ -0,0 +1,35 @@
+// the puzzle text's example, in its own smaller memory space
+
+use aoc_2024::day18::{self, Memory};
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day18.txt");
+
+#[test]
+fn example_exit_steps() {
+    let bytes = day18::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day18::steps_after_fallen(&bytes, Memory::EXAMPLE), Ok(22));
+}
+
+#[test]
+fn example_blocking_byte() {
+    let bytes = day18::input_generator(EXAMPLE).unwrap();
+    assert_eq!(
+        day18::blocking_byte(&bytes, Memory::EXAMPLE.size),
+        Ok("6,1".to_string())
+    );
+}
+
+#[test]
+fn rejects_bytes_outside_the_space() {
+    let bytes = day18::input_generator(EXAMPLE).unwrap();
+    assert!(
+        day18::steps_after_fallen(
+            &bytes,
+            Memory {
+                size: 6,
+                fallen: 12
+            }
+        )
+        .is_err()
+    );
+}
@@ -0,0 +1,17 @@
+// the puzzle text's example towels and designs
+
+use aoc_2024::day19;
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day19.txt");
+
+#[test]
+fn example_possible_designs() {
+    let onsen = day19::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day19::part1(&onsen), 6);
+}
+
+#[test]
+fn example_arrangements() {
+    let onsen = day19::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day19::part2(&onsen), Ok(16));
+}
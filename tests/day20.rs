@@ -0,0 +1,17 @@
+// the puzzle text's example, counted with its own lower thresholds
+
+use aoc_2024::day20::{self, Thresholds};
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day20.txt");
+
+#[test]
+fn example_short_cheats() {
+    let cheats = day20::track_cheats(EXAMPLE, 2, Thresholds::EXAMPLE.part1).unwrap();
+    assert_eq!(cheats, 44);
+}
+
+#[test]
+fn example_long_cheats() {
+    let cheats = day20::track_cheats(EXAMPLE, 20, Thresholds::EXAMPLE.part2).unwrap();
+    assert_eq!(cheats, 285);
+}
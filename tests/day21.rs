@@ -0,0 +1,27 @@
+// the puzzle text's example, and shallow robot chains small enough to count
+// by hand
+
+use aoc_2024::day21;
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day21.txt");
+
+#[test]
+fn example_two_layers() {
+    let codes = day21::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day21::total_complexity(&codes, 2), Ok(126384));
+}
+
+#[test]
+fn typing_on_the_door_directly() {
+    // with no robot in between the presses for 029A are <A ^A >^^A vvvA
+    let codes = vec!["029A".to_string()];
+    assert_eq!(day21::total_complexity(&codes, 0), Ok(12 * 29));
+}
+
+#[test]
+fn one_robot_in_between() {
+    // the puzzle text's second keypad sequence for 029A,
+    // v<<A>>^A<A>AvA<^AA>A<vAAA>^A
+    let codes = vec!["029A".to_string()];
+    assert_eq!(day21::total_complexity(&codes, 1), Ok(28 * 29));
+}
@@ -0,0 +1,34 @@
+// the puzzle text's examples: the secrets that follow 123, the 2000th
+// secrets of the first buyers, and the prices of the second ones
+
+use aoc_2024::day22;
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day22.txt");
+const PRICES: &str = include_str!("../input/2024/examples/day22-prices.txt");
+
+#[test]
+fn example_next_secrets() {
+    let secrets: Vec<u64> = std::iter::successors(Some(123), |&s| Some(day22::next_secret(s)))
+        .skip(1)
+        .take(10)
+        .collect();
+    assert_eq!(
+        secrets,
+        [
+            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+            5908254
+        ]
+    );
+}
+
+#[test]
+fn example_secret_sum() {
+    let secrets = day22::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day22::part1(&secrets), 37327623);
+}
+
+#[test]
+fn example_most_bananas() {
+    let secrets = day22::input_generator(PRICES).unwrap();
+    assert_eq!(day22::part2(&secrets), 23);
+}
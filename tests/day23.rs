@@ -0,0 +1,17 @@
+// the puzzle text's example network
+
+use aoc_2024::day23;
+
+const EXAMPLE: &str = include_str!("../input/2024/examples/day23.txt");
+
+#[test]
+fn example_triangles_with_t() {
+    let network = day23::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day23::part1(&network), 7);
+}
+
+#[test]
+fn example_password() {
+    let network = day23::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day23::part2(&network), "co,de,ka,ta");
+}
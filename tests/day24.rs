@@ -0,0 +1,19 @@
+// the puzzle text's two example circuits; they are not adders, so part 2
+// has nothing to find in them
+
+use aoc_2024::day24;
+
+const SMALL: &str = include_str!("../input/2024/examples/day24-small.txt");
+const EXAMPLE: &str = include_str!("../input/2024/examples/day24.txt");
+
+#[test]
+fn example_small_output() {
+    let circuit = day24::input_generator(SMALL).unwrap();
+    assert_eq!(day24::part1(&circuit), Ok(4));
+}
+
+#[test]
+fn example_output() {
+    let circuit = day24::input_generator(EXAMPLE).unwrap();
+    assert_eq!(day24::part1(&circuit), Ok(2024));
+}
@@ -0,0 +1,96 @@
+// loads the cdylib the way a C caller would and solves the day 1 example
+// from the puzzle text through it
+
+use std::{
+    env::{self, consts},
+    ffi::{CStr, c_char},
+    path::PathBuf,
+};
+
+use libloading::{Library, Symbol};
+
+type SolveFn =
+    unsafe extern "C" fn(u32, u32, u32, *const u8, usize, *mut c_char, usize, *mut usize) -> i32;
+type StrErrorFn = unsafe extern "C" fn(i32) -> *const c_char;
+
+const AOC_OK: i32 = 0;
+const AOC_ERR_UNKNOWN_PUZZLE: i32 = 2;
+const AOC_ERR_SOLVE_FAILED: i32 = 4;
+const AOC_ERR_BUFFER_TOO_SMALL: i32 = 5;
+
+const DAY1_EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+// the test binary is target/<profile>/deps/ffi-<hash>, the library is built
+// next to deps/
+fn library_path() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    let profile_dir = exe.parent().unwrap().parent().unwrap();
+    profile_dir.join(format!(
+        "{}aoc_2024{}",
+        consts::DLL_PREFIX,
+        consts::DLL_SUFFIX
+    ))
+}
+
+fn load() -> Library {
+    let path = library_path();
+    unsafe { Library::new(&path) }.unwrap_or_else(|e| panic!("loading {:?}: {}", path, e))
+}
+
+fn solve(library: &Library, day: u32, part: u32, input: &str, cap: usize) -> (i32, String, usize) {
+    let aoc_solve: Symbol<SolveFn> = unsafe { library.get(b"aoc_solve") }.unwrap();
+    let mut out = vec![0 as c_char; cap];
+    let mut len = 0;
+    let code = unsafe {
+        aoc_solve(
+            2024,
+            day,
+            part,
+            input.as_ptr(),
+            input.len(),
+            out.as_mut_ptr(),
+            out.len(),
+            &mut len,
+        )
+    };
+    let text = match code {
+        AOC_OK | AOC_ERR_SOLVE_FAILED => unsafe { CStr::from_ptr(out.as_ptr()) }
+            .to_string_lossy()
+            .into_owned(),
+        _ => String::new(),
+    };
+    (code, text, len)
+}
+
+#[test]
+fn solves_day1_example() {
+    let library = load();
+    assert_eq!(
+        solve(&library, 1, 1, DAY1_EXAMPLE, 64),
+        (AOC_OK, "11".to_string(), 2)
+    );
+    assert_eq!(
+        solve(&library, 1, 2, DAY1_EXAMPLE, 64),
+        (AOC_OK, "31".to_string(), 2)
+    );
+}
+
+#[test]
+fn reports_errors() {
+    let library = load();
+    assert_eq!(
+        solve(&library, 1, 1, DAY1_EXAMPLE, 2),
+        (AOC_ERR_BUFFER_TOO_SMALL, String::new(), 3)
+    );
+    assert_eq!(
+        solve(&library, 26, 1, DAY1_EXAMPLE, 64).0,
+        AOC_ERR_UNKNOWN_PUZZLE
+    );
+    let (code, message, _) = solve(&library, 1, 1, "3 x\n", 256);
+    assert_eq!(code, AOC_ERR_SOLVE_FAILED);
+    assert!(!message.is_empty());
+
+    let strerror: Symbol<StrErrorFn> = unsafe { library.get(b"aoc_strerror") }.unwrap();
+    let message = unsafe { CStr::from_ptr(strerror(AOC_ERR_BUFFER_TOO_SMALL)) };
+    assert_eq!(message.to_str().unwrap(), "output buffer too small");
+}
@@ -0,0 +1,35 @@
+// peak memory is process-wide, so it is only reported while nothing else
+// could be allocating, and a timed-out run keeps going in the background
+
+use std::time::Duration;
+
+use aoc_2024::{registry, runner, stress};
+
+const DAY1_EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+#[test]
+fn no_peak_memory_while_a_timed_out_run_goes_on() {
+    let fast = registry::implementations(1, 1)[0];
+    assert!(
+        runner::run_solution(fast, DAY1_EXAMPLE)
+            .peak_memory
+            .is_some()
+    );
+
+    // the brute force tries every operator combination, far more than 1ms
+    let slow = registry::implementations(7, 2)
+        .into_iter()
+        .find(|solution| solution.label() == "naive")
+        .unwrap();
+    let input = stress::generate(7, stress::DEFAULT_SEED, 1).unwrap();
+    runner::set_timeout(Some(Duration::from_millis(1)));
+    let run = runner::run_solution(slow, &input);
+    runner::set_timeout(None);
+    assert!(run.timed_out);
+    assert_eq!(run.peak_memory, None);
+
+    assert!(runner::abandoned() > 0);
+    let run = runner::run_solution(fast, DAY1_EXAMPLE);
+    assert_eq!(run.answer, Ok("11".to_string()));
+    assert_eq!(run.peak_memory, None);
+}